@@ -0,0 +1,45 @@
+//! Embeds the puzzle examples below `examples/` into the library, so they can
+//! be used from any working directory.
+
+use std::env;
+use std::fmt::Write;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+fn main() {
+    let root = PathBuf::from(env::var("CARGO_MANIFEST_DIR").unwrap()).join("examples");
+    println!("cargo:rerun-if-changed={}", root.display());
+
+    let mut files = Vec::new();
+    if let Ok(days) = fs::read_dir(&root) {
+        for dir in days.flatten() {
+            let Some(day) = dir
+                .file_name()
+                .to_str()
+                .and_then(|n| n.strip_prefix("day"))
+                .and_then(|n| n.parse::<u32>().ok())
+            else {
+                continue;
+            };
+            for file in fs::read_dir(dir.path()).unwrap().flatten() {
+                if file.file_type().unwrap().is_file() {
+                    files.push((day, file.file_name().into_string().unwrap(), file.path()));
+                }
+            }
+        }
+    }
+    files.sort();
+
+    let mut out = String::from("&[\n");
+    for (day, name, path) in &files {
+        writeln!(
+            out,
+            "    ({day}, {name:?}, include_str!({:?})),",
+            path.to_str().unwrap()
+        )
+        .unwrap();
+    }
+    out += "]\n";
+    let dest = Path::new(&env::var("OUT_DIR").unwrap()).join("examples.rs");
+    fs::write(dest, out).unwrap();
+}
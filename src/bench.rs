@@ -0,0 +1,225 @@
+use crate::parse::ParseError;
+use crate::solution::DynSolution;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Stage {
+    Parse,
+    Part1,
+    Part2,
+}
+
+impl fmt::Display for Stage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let out = match self {
+            Stage::Parse => "parse",
+            Stage::Part1 => "part1",
+            Stage::Part2 => "part2",
+        };
+        write!(f, "{out}")
+    }
+}
+
+/// Timing summary of one stage of one day, in nanoseconds.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Record {
+    pub day: u32,
+    pub stage: Stage,
+    pub input: String,
+    pub min_ns: u64,
+    pub median_ns: u64,
+    pub p95_ns: u64,
+}
+
+impl Record {
+    fn new(day: u32, stage: Stage, input: &str, samples: &mut [Duration]) -> Self {
+        assert!(!samples.is_empty(), "No samples");
+        samples.sort();
+        let nanos = |d: Duration| d.as_nanos().try_into().unwrap_or(u64::MAX);
+        // Nearest-rank percentile.
+        let p95 = (samples.len() * 95).div_ceil(100) - 1;
+        Self {
+            day,
+            stage,
+            input: input.to_string(),
+            min_ns: nanos(samples[0]),
+            median_ns: nanos(samples[samples.len() / 2]),
+            p95_ns: nanos(samples[p95]),
+        }
+    }
+}
+
+/// How a record compares to the same record of a previous report.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Comparison {
+    /// Median of the previous record.
+    pub previous_ns: u64,
+    /// Relative change of the median in percent, `None` if the previous one was zero.
+    pub change: Option<f64>,
+    /// Whether the median slowed down by more than the threshold.
+    pub regressed: bool,
+}
+
+/// Results of a benchmark run, as written to the JSON report.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Report {
+    pub runs: usize,
+    pub records: Vec<Record>,
+}
+
+impl Report {
+    pub fn new(runs: usize) -> Self {
+        Self {
+            runs,
+            records: Vec::new(),
+        }
+    }
+
+    /// Parses and solves `input` with `day` `self.runs` times, adding a record per stage.
+    pub fn bench(
+        &mut self,
+        day: &dyn DynSolution,
+        input_name: &str,
+        input: &str,
+    ) -> Result<(), ParseError> {
+        let mut samples = [
+            Vec::with_capacity(self.runs),
+            Vec::with_capacity(self.runs),
+            Vec::with_capacity(self.runs),
+        ];
+        for _ in 0..self.runs {
+            let start = Instant::now();
+            let parsed = day.parse(black_box(input))?;
+            samples[0].push(start.elapsed());
+            let start = Instant::now();
+            black_box(parsed.part1());
+            samples[1].push(start.elapsed());
+            let start = Instant::now();
+            black_box(parsed.part2());
+            samples[2].push(start.elapsed());
+        }
+        for (stage, s) in [Stage::Parse, Stage::Part1, Stage::Part2]
+            .into_iter()
+            .zip(samples.iter_mut())
+        {
+            self.records
+                .push(Record::new(day.day(), stage, input_name, s));
+        }
+        Ok(())
+    }
+
+    pub fn find(&self, day: u32, stage: Stage, input: &str) -> Option<&Record> {
+        self.records
+            .iter()
+            .find(|r| r.day == day && r.stage == stage && r.input == input)
+    }
+
+    /// Relative change of the median of `record` compared to the same record in `self`, in percent.
+    pub fn change(&self, record: &Record) -> Option<f64> {
+        let prev = self.find(record.day, record.stage, &record.input)?;
+        if prev.median_ns == 0 {
+            return None;
+        }
+        // Dividing last keeps round changes exact, so they compare as expected to a threshold.
+        let prev_ns = prev.median_ns as f64;
+        Some((record.median_ns as f64 - prev_ns) * 100.0 / prev_ns)
+    }
+
+    /// Compares `record` to the same record in `self`, counting a slowdown of
+    /// more than `threshold` percent as a regression.
+    pub fn compare(&self, record: &Record, threshold: f64) -> Option<Comparison> {
+        let prev = self.find(record.day, record.stage, &record.input)?;
+        let change = self.change(record);
+        Some(Comparison {
+            previous_ns: prev.median_ns,
+            change,
+            regressed: change.is_some_and(|c| c > threshold),
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::days::day1::Day1;
+
+    #[test]
+    fn test_record() {
+        let mut samples: Vec<_> = (1..=20).rev().map(Duration::from_nanos).collect();
+        let r = Record::new(1, Stage::Parse, "input/day1", &mut samples);
+        assert_eq!((r.min_ns, r.median_ns, r.p95_ns), (1, 11, 19));
+        let r = Record::new(
+            1,
+            Stage::Parse,
+            "input/day1",
+            &mut [Duration::from_nanos(5)],
+        );
+        assert_eq!((r.min_ns, r.median_ns, r.p95_ns), (5, 5, 5));
+    }
+
+    #[test]
+    fn test_bench() {
+        let mut report = Report::new(3);
+        report.bench(&Day1, "example", "3   4\n4   3\n").unwrap();
+        assert_eq!(report.records.len(), 3);
+        assert!(report.find(1, Stage::Part2, "example").is_some());
+        assert!(report.bench(&Day1, "bad", "3\n").is_err());
+
+        let json = serde_json::to_string(&report).unwrap();
+        let prev: Report = serde_json::from_str(&json).unwrap();
+        assert_eq!(prev, report);
+    }
+
+    #[test]
+    fn test_compare() {
+        let record = |stage, median_ns| Record {
+            day: 1,
+            stage,
+            input: "input/day1".to_string(),
+            min_ns: median_ns,
+            median_ns,
+            p95_ns: median_ns,
+        };
+        let prev = Report {
+            runs: 3,
+            records: vec![
+                record(Stage::Parse, 1000),
+                record(Stage::Part1, 200),
+                record(Stage::Part2, 0),
+            ],
+        };
+        let compare = |stage, median_ns| prev.compare(&record(stage, median_ns), 10.0);
+        let comparison = |previous_ns, change, regressed| {
+            Some(Comparison {
+                previous_ns,
+                change,
+                regressed,
+            })
+        };
+        // Exactly at the threshold is not a regression yet.
+        assert_eq!(
+            compare(Stage::Parse, 1100),
+            comparison(1000, Some(10.0), false)
+        );
+        assert_eq!(
+            compare(Stage::Parse, 1101),
+            comparison(1000, Some(10.1), true)
+        );
+        assert_eq!(
+            compare(Stage::Part1, 500),
+            comparison(200, Some(150.0), true)
+        );
+        assert_eq!(
+            compare(Stage::Part1, 100),
+            comparison(200, Some(-50.0), false)
+        );
+        assert_eq!(compare(Stage::Part2, 100), comparison(0, None, false));
+        let mut other = record(Stage::Parse, 1000);
+        other.input = "example".to_string();
+        assert_eq!(prev.compare(&other, 10.0), None);
+    }
+}
@@ -0,0 +1,121 @@
+use crate::Days;
+use aoc_2024::bench::Report;
+use aoc_2024::table::Table;
+use clap::Args;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+use std::time::Duration;
+
+#[derive(Args, Debug)]
+pub struct BenchArgs {
+    #[command(flatten)]
+    days: Days,
+    /// Input file, `-` for stdin, defaults to `dayN` in the input directory
+    #[arg(short, long, conflicts_with = "all")]
+    input: Option<PathBuf>,
+    /// Number of runs per day
+    #[arg(short = 'n', long, default_value_t = 10, value_parser = clap::value_parser!(u64).range(1..))]
+    runs: u64,
+    /// Where to write the JSON report
+    #[arg(short, long, default_value = "bench.json")]
+    output: PathBuf,
+    /// Previous JSON report to compare against
+    #[arg(short, long)]
+    compare: Option<PathBuf>,
+    /// Slowdown of the median in percent above which a stage counts as regressed
+    #[arg(short, long, default_value_t = 10.0)]
+    threshold: f64,
+}
+
+fn load_report(path: &PathBuf) -> Result<Report, String> {
+    let f = fs::read_to_string(path).map_err(|e| format!("Cannot read {}: {e}", path.display()))?;
+    serde_json::from_str(&f).map_err(|e| format!("Cannot parse {}: {e}", path.display()))
+}
+
+pub fn bench(args: BenchArgs, input_dir: &Path) -> ExitCode {
+    let selected = match args.days.select() {
+        Ok(s) => s,
+        Err(e) => {
+            eprintln!("{e}");
+            return ExitCode::FAILURE;
+        }
+    };
+    let previous = match args.compare.as_ref().map(load_report).transpose() {
+        Ok(p) => p,
+        Err(e) => {
+            eprintln!("{e}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let mut ok = true;
+    let mut report = Report::new(args.runs as usize);
+    for day in selected {
+        let path = match &args.input {
+            Some(path) => crate::resolve_input(input_dir, day.day(), path),
+            None => crate::default_input(input_dir, day.day()),
+        };
+        let f = match crate::read_input(&path) {
+            Ok(f) => f,
+            Err(e) => {
+                eprintln!("Day {}: cannot read {}: {e}", day.day(), path.display());
+                ok = false;
+                continue;
+            }
+        };
+        if let Err(e) = report.bench(day, &path.display().to_string(), &f) {
+            eprintln!("{}: {}", path.display(), e.diagnostic());
+            ok = false;
+        }
+    }
+
+    let mut header = vec!["day", "stage", "min", "median", "p95"];
+    if previous.is_some() {
+        header.extend(["previous", "change", ""]);
+    }
+    let mut table = Table::new(header);
+    let mut regressions = 0;
+    let fmt = |ns: u64| format!("{:?}", Duration::from_nanos(ns));
+    for r in &report.records {
+        let mut row = vec![
+            r.day.to_string(),
+            r.stage.to_string(),
+            fmt(r.min_ns),
+            fmt(r.median_ns),
+            fmt(r.p95_ns),
+        ];
+        if let Some(previous) = &previous {
+            match previous.compare(r, args.threshold) {
+                Some(c) => {
+                    row.push(fmt(c.previous_ns));
+                    if let Some(change) = c.change {
+                        row.push(format!("{change:+.1}%"));
+                    }
+                    if c.regressed {
+                        regressions += 1;
+                        row.push("REGRESSION".to_string());
+                    }
+                }
+                None => row.push("-".to_string()),
+            }
+        }
+        table.row(row);
+    }
+    print!("{table}");
+
+    let json = serde_json::to_string_pretty(&report).expect("Report is serializable");
+    if let Err(e) = fs::write(&args.output, json) {
+        eprintln!("Cannot write {}: {e}", args.output.display());
+        return ExitCode::FAILURE;
+    }
+    if regressions > 0 {
+        println!("{regressions} regressions above {}%", args.threshold);
+        ok = false;
+    }
+    if ok {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::FAILURE
+    }
+}
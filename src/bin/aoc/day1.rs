@@ -0,0 +1,216 @@
+use aoc_2024::days::day1::incremental::LocationLists;
+use aoc_2024::days::day1::metric::{self, CostTable, Metric};
+use aoc_2024::days::day1::report;
+use aoc_2024::days::day1::{external, Day1};
+use aoc_2024::solution::Solution;
+use clap::{Args, Subcommand, ValueEnum};
+use std::fs::{self, File};
+use std::io::{self, BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+
+#[derive(Args, Debug)]
+pub struct Day1Args {
+    #[command(subcommand)]
+    command: Day1Command,
+}
+
+#[derive(Subcommand, Debug)]
+enum Day1Command {
+    /// Solve part 1 on lists too large for memory by sorting them on disk
+    External(ExternalArgs),
+    /// Pair up the lists at minimum total cost under a chosen metric
+    Match(MatchArgs),
+    /// Keep both answers up to date under edits streamed on stdin
+    ///
+    /// Reads one command per line: `insert left|right ID`, `remove left|right ID`,
+    /// `distance`, `similarity`, `len` or `quit`, and answers each with one line,
+    /// starting with `error:` if it failed.
+    Serve(ServeArgs),
+    /// Compare the lists as multisets
+    Report(ReportArgs),
+}
+
+#[derive(Args, Debug)]
+struct ExternalArgs {
+    /// Input file, `-` for stdin, defaults to `day1` in the input directory
+    input: Option<PathBuf>,
+    /// Memory to hold location IDs in, e.g. 512K, 64M or 2G, at least 16K
+    #[arg(short, long, default_value = "256M", value_parser = external::parse_size)]
+    memory: usize,
+    /// Directory for the sorted runs, defaults to the system's temporary directory
+    #[arg(long)]
+    tmp_dir: Option<PathBuf>,
+}
+
+#[derive(Args, Debug)]
+struct MatchArgs {
+    /// Input file, `-` for stdin, defaults to `day1` in the input directory
+    input: Option<PathBuf>,
+    /// Cost of pairing two IDs
+    #[arg(short, long, value_enum, default_value_t = MetricName::Absolute)]
+    metric: MetricName,
+    /// File of `LEFT RIGHT COST` lines for the table metric
+    #[arg(long, required_if_eq("metric", "table"))]
+    costs: Option<PathBuf>,
+    /// Print every matched pair with its cost
+    #[arg(long)]
+    pairs: bool,
+}
+
+#[derive(Args, Debug)]
+struct ServeArgs {
+    /// Input file with the lists to start from, empty lists if not given
+    input: Option<PathBuf>,
+}
+
+#[derive(Args, Debug)]
+struct ReportArgs {
+    /// Input file, `-` for stdin, defaults to `day1` in the input directory
+    input: Option<PathBuf>,
+    /// Number of top contributors to the similarity score to list
+    #[arg(short = 'n', long, default_value_t = 10)]
+    top: usize,
+    /// Output format
+    #[arg(short, long, value_enum, default_value_t = ReportFormat::Text)]
+    format: ReportFormat,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+enum ReportFormat {
+    /// Summary and tables for reading
+    Text,
+    /// One JSON object
+    Json,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+enum MetricName {
+    /// |l - r|, the puzzle's distance
+    Absolute,
+    /// (l - r)²
+    Squared,
+    /// |ln(l / r)|, for positive IDs
+    LogRatio,
+    /// Costs from `--costs`, the absolute difference for pairs not listed
+    Table,
+}
+
+/// Where to read the input of day 1 from, without reading it all.
+fn open_input(input_dir: &Path, path: Option<&PathBuf>) -> Result<Box<dyn BufRead>, String> {
+    let path = match path {
+        Some(path) => crate::resolve_input(input_dir, 1, path),
+        None => crate::default_input(input_dir, 1),
+    };
+    if crate::is_stdin(&path) {
+        return Ok(Box::new(io::stdin().lock()));
+    }
+    let file = File::open(&path).map_err(|e| format!("Cannot read {}: {e}", path.display()))?;
+    Ok(Box::new(BufReader::new(file)))
+}
+
+pub fn day1(args: Day1Args, input_dir: &Path) -> ExitCode {
+    let res = match args.command {
+        Day1Command::External(args) => run_external(args, input_dir),
+        Day1Command::Match(args) => run_match(args, input_dir),
+        Day1Command::Serve(args) => serve(args, input_dir),
+        Day1Command::Report(args) => run_report(args, input_dir),
+    };
+    match res {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("{e}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn run_external(args: ExternalArgs, input_dir: &Path) -> Result<(), String> {
+    let input = open_input(input_dir, args.input.as_ref())?;
+    let tmp_dir = args.tmp_dir.unwrap_or_else(std::env::temp_dir);
+    let res = external::part1_external(input, args.memory, &tmp_dir).map_err(|e| e.to_string())?;
+    println!("Day 1 part 1: {}", res.distance);
+    if res.runs > 0 {
+        eprintln!("Merged {} sorted runs per list", res.runs);
+    }
+    Ok(())
+}
+
+/// Reads and parses the whole input of day 1.
+fn load_lists(input_dir: &Path, path: Option<&PathBuf>) -> Result<(Vec<u64>, Vec<u64>), String> {
+    let path = match path {
+        Some(path) => crate::resolve_input(input_dir, 1, path),
+        None => crate::default_input(input_dir, 1),
+    };
+    let input =
+        crate::read_input(&path).map_err(|e| format!("Cannot read {}: {e}", path.display()))?;
+    Day1::parse(&input).map_err(|e| format!("{}: {}", path.display(), e.diagnostic()))
+}
+
+fn run_match(args: MatchArgs, input_dir: &Path) -> Result<(), String> {
+    let (left, right) = load_lists(input_dir, args.input.as_ref())?;
+    let metric = match args.metric {
+        MetricName::Absolute => Metric::Absolute,
+        MetricName::Squared => Metric::Squared,
+        MetricName::LogRatio => Metric::LogRatio,
+        MetricName::Table => {
+            let path = args.costs.expect("clap requires --costs");
+            let costs = fs::read_to_string(&path)
+                .map_err(|e| format!("Cannot read {}: {e}", path.display()))?;
+            Metric::Table(
+                CostTable::parse(&costs)
+                    .map_err(|e| format!("{}: {}", path.display(), e.diagnostic()))?,
+            )
+        }
+    };
+    let matching = metric::min_cost_matching(&left, &right, &metric)?;
+    if args.pairs {
+        for &(l, r) in &matching.pairs {
+            println!("{l} {r} {}", metric.pair_cost(l, r));
+        }
+    }
+    println!("Minimum total cost: {}", matching.cost);
+    eprintln!(
+        "Matched {} pairs by {}",
+        matching.pairs.len(),
+        matching.method
+    );
+    Ok(())
+}
+
+fn serve(args: ServeArgs, input_dir: &Path) -> Result<(), String> {
+    let mut lists = match &args.input {
+        // Commands come from stdin, so the input cannot.
+        Some(path) if crate::is_stdin(path) => {
+            return Err("The lists cannot be read from stdin while serving".to_string())
+        }
+        Some(path) => {
+            let (left, right) = load_lists(input_dir, Some(path))?;
+            LocationLists::from_lists(&left, &right)
+        }
+        None => LocationLists::new(),
+    };
+    let mut out = io::stdout().lock();
+    for line in io::stdin().lock().lines() {
+        let line = line.map_err(|e| format!("Cannot read command: {e}"))?;
+        let reply = match lists.execute(&line) {
+            Ok(Some(reply)) => reply,
+            Ok(None) => break,
+            Err(e) => format!("error: {e}"),
+        };
+        writeln!(out, "{reply}")
+            .and_then(|()| out.flush())
+            .map_err(|e| format!("Cannot write reply: {e}"))?;
+    }
+    Ok(())
+}
+
+fn run_report(args: ReportArgs, input_dir: &Path) -> Result<(), String> {
+    let (left, right) = load_lists(input_dir, args.input.as_ref())?;
+    let report = report::report(&left, &right, args.top);
+    match args.format {
+        ReportFormat::Text => print!("{report}"),
+        ReportFormat::Json => println!("{}", report.to_json()),
+    }
+    Ok(())
+}
@@ -0,0 +1,62 @@
+use aoc_2024::days::day2::{self, Day2};
+use aoc_2024::solution::Solution;
+use clap::{Args, Subcommand};
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+
+#[derive(Args, Debug)]
+pub struct Day2Args {
+    #[command(subcommand)]
+    command: Day2Command,
+}
+
+#[derive(Subcommand, Debug)]
+enum Day2Command {
+    /// Count the reports made safe by removing some levels
+    Safe(SafeArgs),
+}
+
+#[derive(Args, Debug)]
+struct SafeArgs {
+    /// Input file, `-` for stdin, defaults to `day2` in the input directory
+    input: Option<PathBuf>,
+    /// Most levels to remove from each report, 1 for part 2
+    #[arg(short, long, default_value_t = 1)]
+    tolerance: usize,
+}
+
+pub fn day2(args: Day2Args, input_dir: &Path) -> ExitCode {
+    let res = match args.command {
+        Day2Command::Safe(args) => safe(args, input_dir),
+    };
+    match res {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("{e}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn safe(args: SafeArgs, input_dir: &Path) -> Result<(), String> {
+    let path = match &args.input {
+        Some(path) => crate::resolve_input(input_dir, 2, path),
+        None => crate::default_input(input_dir, 2),
+    };
+    let input =
+        crate::read_input(&path).map_err(|e| format!("Cannot read {}: {e}", path.display()))?;
+    let reports =
+        Day2::parse(&input).map_err(|e| format!("{}: {}", path.display(), e.diagnostic()))?;
+    let safe = day2::count_safe(&reports, args.tolerance);
+    let levels = if args.tolerance == 1 {
+        "level"
+    } else {
+        "levels"
+    };
+    println!(
+        "{safe} of {} reports are safe with up to {} {levels} removed",
+        reports.len(),
+        args.tolerance
+    );
+    Ok(())
+}
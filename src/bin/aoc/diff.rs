@@ -0,0 +1,47 @@
+use crate::Days;
+use aoc_2024::differential;
+use clap::Args;
+use std::process::ExitCode;
+
+#[derive(Args, Debug)]
+pub struct DiffArgs {
+    #[command(flatten)]
+    days: Days,
+    /// Number of generated inputs per day
+    #[arg(short, long, default_value_t = 100)]
+    cases: u64,
+    /// First seed to generate inputs from
+    #[arg(short, long, default_value_t = 0)]
+    seed: u64,
+    /// Scale of the generated inputs
+    #[arg(short = 'n', long, default_value_t = 20)]
+    size: usize,
+}
+
+pub fn diff(args: DiffArgs) -> ExitCode {
+    let days = match args.days.select() {
+        Ok(days) => days,
+        Err(e) => {
+            eprintln!("{e}");
+            return ExitCode::FAILURE;
+        }
+    };
+    let mut failed = false;
+    for day in days {
+        let seeds = args.seed..args.seed + args.cases;
+        let report = differential::check(day, seeds, args.size);
+        match report.mismatch {
+            Some(mismatch) => {
+                println!("{mismatch}");
+                failed = true;
+            }
+            None if report.checked == 0 => println!("Day {}: no reference", day.day()),
+            None => println!("Day {}: {} checks agree", day.day(), report.checked),
+        }
+    }
+    if failed {
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    }
+}
@@ -0,0 +1,48 @@
+use crate::Days;
+use aoc_2024::client::{self, Client, Config, Ureq};
+use clap::Args;
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+
+#[derive(Args, Debug)]
+pub struct FetchArgs {
+    #[command(flatten)]
+    days: Days,
+    /// Config file with the session token
+    #[arg(long, default_value = client::DEFAULT_CONFIG)]
+    config: PathBuf,
+}
+
+pub fn fetch(args: FetchArgs, input_dir: &Path) -> ExitCode {
+    let selected = match args.days.select() {
+        Ok(s) => s,
+        Err(e) => {
+            eprintln!("{e}");
+            return ExitCode::FAILURE;
+        }
+    };
+    let config = match Config::load(&args.config) {
+        Ok(c) => c,
+        Err(e) => {
+            eprintln!("Cannot load {}: {e}", args.config.display());
+            return ExitCode::FAILURE;
+        }
+    };
+    let client = Client::new(Ureq::new(), &config);
+    let mut ok = true;
+    for day in selected {
+        match client.fetch_input(day.day(), input_dir) {
+            Ok((path, true)) => println!("Day {}: downloaded {}", day.day(), path.display()),
+            Ok((path, false)) => println!("Day {}: cached in {}", day.day(), path.display()),
+            Err(e) => {
+                eprintln!("Day {}: {e}", day.day());
+                ok = false;
+            }
+        }
+    }
+    if ok {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::FAILURE
+    }
+}
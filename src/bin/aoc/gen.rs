@@ -0,0 +1,42 @@
+use aoc_2024::days;
+use aoc_2024::rng::Rng;
+use clap::Args;
+use std::fs;
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+#[derive(Args, Debug)]
+pub struct GenArgs {
+    /// Day to generate an input for
+    day: u32,
+    /// Seed of the random number generator
+    #[arg(short, long, default_value_t = 0)]
+    seed: u64,
+    /// Scale of the input, e.g. number of lines or grid side length
+    #[arg(short = 'n', long, default_value_t = 100)]
+    size: usize,
+    /// Write to a file instead of stdout
+    #[arg(short, long)]
+    output: Option<PathBuf>,
+}
+
+pub fn gen(args: GenArgs) -> ExitCode {
+    let Some(day) = days::get(args.day) else {
+        eprintln!("Day {} is not implemented", args.day);
+        return ExitCode::FAILURE;
+    };
+    let Some(input) = day.generate(&mut Rng::new(args.seed), args.size) else {
+        eprintln!("Day {} has no input generator", args.day);
+        return ExitCode::FAILURE;
+    };
+    match args.output {
+        Some(path) => {
+            if let Err(e) = fs::write(&path, input) {
+                eprintln!("Cannot write {}: {e}", path.display());
+                return ExitCode::FAILURE;
+            }
+        }
+        None => print!("{input}"),
+    }
+    ExitCode::SUCCESS
+}
@@ -0,0 +1,145 @@
+mod bench;
+mod day1;
+mod day2;
+mod diff;
+mod fetch;
+mod gen;
+mod new;
+mod render;
+mod run;
+mod step;
+mod submit;
+mod verify;
+
+use aoc_2024::days;
+use aoc_2024::solution::{DynSolution, Parsed};
+use clap::{Args, Parser, Subcommand};
+use std::fs;
+use std::io::{self, Read};
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+
+#[derive(Parser, Debug)]
+struct Cli {
+    /// Directory holding the `dayN` input files
+    #[arg(long, global = true, env = "AOC_INPUT_DIR", default_value = "input")]
+    input_dir: PathBuf,
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Solve one or all days
+    Run(run::RunArgs),
+    /// Check answers against the ledger
+    Verify(verify::VerifyArgs),
+    /// Time parsing and both parts
+    Bench(bench::BenchArgs),
+    /// Generate a random input
+    Gen(gen::GenArgs),
+    /// Compare optimised parts against their reference implementations
+    Diff(diff::DiffArgs),
+    /// Download puzzle inputs into the input directory
+    Fetch(fetch::FetchArgs),
+    /// Submit an answer and record the verdict
+    Submit(submit::SubmitArgs),
+    /// Start a new day from the template
+    New(new::NewArgs),
+    /// Draw a day's solution as an image or animation
+    Render(render::RenderArgs),
+    /// Step through a day's simulation interactively
+    Step(step::StepArgs),
+    /// Tools for day 1's location lists beyond the puzzle
+    Day1(day1::Day1Args),
+    /// Tools for day 2's reports beyond the puzzle
+    Day2(day2::Day2Args),
+}
+
+/// Selects either a single day or all registered days.
+#[derive(Args, Debug)]
+struct Days {
+    /// Day to use
+    #[arg(required_unless_present = "all")]
+    day: Option<u32>,
+    /// Use all registered days
+    #[arg(long, conflicts_with = "day")]
+    all: bool,
+}
+
+impl Days {
+    fn select(&self) -> Result<Vec<&'static dyn DynSolution>, String> {
+        if self.all {
+            return Ok(days::DAYS.to_vec());
+        }
+        let day = self.day.unwrap();
+        days::get(day)
+            .map(|d| vec![d])
+            .ok_or_else(|| format!("Day {day} is not implemented"))
+    }
+}
+
+fn default_input(input_dir: &Path, day: u32) -> PathBuf {
+    input_dir.join(format!("day{day}"))
+}
+
+/// Where to find the input of `day` given on the command line as `path`.
+///
+/// `-` stands for stdin. Relative paths missing from the working directory are
+/// looked up in `input_dir`, and directories stand for the `dayN` file inside.
+fn resolve_input(input_dir: &Path, day: u32, path: &Path) -> PathBuf {
+    if is_stdin(path) {
+        return path.to_path_buf();
+    }
+    let path = if path.exists() {
+        path.to_path_buf()
+    } else {
+        input_dir.join(path)
+    };
+    if path.is_dir() {
+        default_input(&path, day)
+    } else {
+        path
+    }
+}
+
+fn is_stdin(path: &Path) -> bool {
+    path == Path::new("-")
+}
+
+/// Reads `path`, or stdin for `-`.
+fn read_input(path: &Path) -> io::Result<String> {
+    if is_stdin(path) {
+        let mut s = String::new();
+        io::stdin().read_to_string(&mut s)?;
+        Ok(s)
+    } else {
+        fs::read_to_string(path)
+    }
+}
+
+/// Reads and parses the input of `day`, rendering any error for the user.
+fn load(day: &dyn DynSolution, path: &Path) -> Result<Box<dyn Parsed>, String> {
+    let f = read_input(path)
+        .map_err(|e| format!("Day {}: cannot read {}: {e}", day.day(), path.display()))?;
+    day.parse(&f)
+        .map_err(|e| format!("{}: {}", path.display(), e.diagnostic()))
+}
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+    match cli.command {
+        Command::Run(args) => run::run(args, &cli.input_dir),
+        Command::Verify(args) => verify::verify(args, &cli.input_dir),
+        Command::Bench(args) => bench::bench(args, &cli.input_dir),
+        Command::Gen(args) => gen::gen(args),
+        Command::Diff(args) => diff::diff(args),
+        Command::Fetch(args) => fetch::fetch(args, &cli.input_dir),
+        Command::Submit(args) => submit::submit(args, &cli.input_dir),
+        Command::New(args) => new::new(args, &cli.input_dir),
+        Command::Render(args) => render::render(args, &cli.input_dir),
+        Command::Step(args) => step::step(args, &cli.input_dir),
+        Command::Day1(args) => day1::day1(args, &cli.input_dir),
+        Command::Day2(args) => day2::day2(args, &cli.input_dir),
+    }
+}
@@ -0,0 +1,61 @@
+use aoc_2024::scaffold;
+use clap::Args;
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+
+#[derive(Args, Debug)]
+pub struct NewArgs {
+    /// Day to start
+    day: u32,
+    /// Root of the crate to add the day to, defaults to the nearest directory
+    /// at or above the working directory with a Cargo.toml and src/days/mod.rs
+    #[arg(long)]
+    root: Option<PathBuf>,
+}
+
+/// `root` if given, otherwise the crate the working directory is in.
+fn crate_root(root: Option<PathBuf>) -> Result<PathBuf, String> {
+    if let Some(root) = root {
+        return Ok(root);
+    }
+    let cwd =
+        std::env::current_dir().map_err(|e| format!("Cannot find the working directory: {e}"))?;
+    scaffold::find_root(&cwd).ok_or_else(|| {
+        format!(
+            "No crate with {} at or above {}, pass --root",
+            scaffold::REGISTRY,
+            cwd.display()
+        )
+    })
+}
+
+pub fn new(args: NewArgs, input_dir: &Path) -> ExitCode {
+    let root = match crate_root(args.root) {
+        Ok(root) => root,
+        Err(e) => {
+            eprintln!("{e}");
+            return ExitCode::FAILURE;
+        }
+    };
+    // The input directory stays relative to the working directory, wherever
+    // the crate root is.
+    let input_dir = match std::path::absolute(input_dir) {
+        Ok(dir) => dir,
+        Err(e) => {
+            eprintln!("Cannot resolve {}: {e}", input_dir.display());
+            return ExitCode::FAILURE;
+        }
+    };
+    match scaffold::create(&root, &input_dir, args.day) {
+        Ok(created) => {
+            for path in created {
+                println!("Wrote {}", path.display());
+            }
+            ExitCode::SUCCESS
+        }
+        Err(e) => {
+            eprintln!("{e}");
+            ExitCode::FAILURE
+        }
+    }
+}
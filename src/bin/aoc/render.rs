@@ -0,0 +1,63 @@
+use aoc_2024::days;
+use aoc_2024::render::{self, Export};
+use clap::Args;
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+
+#[derive(Args, Debug)]
+pub struct RenderArgs {
+    /// Day to draw
+    day: u32,
+    /// Input file, `-` for stdin, defaults to `dayN` in the input directory
+    #[arg(short, long)]
+    input: Option<PathBuf>,
+    /// Image to write: `.ppm` or `.png` for the final state, `.gif` for an animation
+    #[arg(short, long)]
+    output: PathBuf,
+    /// Side length in pixels of one grid cell
+    #[arg(long, default_value_t = 4, value_parser = clap::value_parser!(u64).range(1..))]
+    scale: u64,
+    /// Only animate every Nth frame, the last one is always shown
+    #[arg(long, default_value_t = 1, value_parser = clap::value_parser!(u64).range(1..))]
+    every: u64,
+    /// Hundredths of a second each animation frame is shown
+    #[arg(long, default_value_t = 4)]
+    delay: u16,
+}
+
+pub fn render(args: RenderArgs, input_dir: &Path) -> ExitCode {
+    let Some(day) = days::get(args.day) else {
+        eprintln!("Day {} is not implemented", args.day);
+        return ExitCode::FAILURE;
+    };
+    let path = match &args.input {
+        Some(path) => crate::resolve_input(input_dir, args.day, path),
+        None => crate::default_input(input_dir, args.day),
+    };
+    let parsed = match crate::load(day, &path) {
+        Ok(p) => p,
+        Err(e) => {
+            eprintln!("{e}");
+            return ExitCode::FAILURE;
+        }
+    };
+    let Some(frames) = parsed.frames() else {
+        eprintln!("Day {} has no visualisation", args.day);
+        return ExitCode::FAILURE;
+    };
+    let options = Export {
+        scale: args.scale as usize,
+        every: args.every as usize,
+        delay: args.delay,
+    };
+    match render::export(frames, &args.output, options) {
+        Ok(n) => {
+            println!("Wrote {n} frame(s) to {}", args.output.display());
+            ExitCode::SUCCESS
+        }
+        Err(e) => {
+            eprintln!("Cannot write {}: {e}", args.output.display());
+            ExitCode::FAILURE
+        }
+    }
+}
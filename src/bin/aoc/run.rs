@@ -0,0 +1,282 @@
+use crate::Days;
+use aoc_2024::examples;
+use aoc_2024::ledger::{self, Ledger};
+use aoc_2024::output::{self, Format, Record};
+use aoc_2024::parallel::{self, Job};
+use aoc_2024::solution::DynSolution;
+use aoc_2024::table::Table;
+use clap::Args;
+use std::num::NonZeroUsize;
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+use std::thread;
+use std::time::{Duration, Instant};
+
+#[derive(Args, Debug)]
+pub struct RunArgs {
+    #[command(flatten)]
+    days: Days,
+    /// Only run the given part
+    #[arg(short, long, value_parser = clap::value_parser!(u8).range(1..=2))]
+    part: Option<u8>,
+    /// Input files, `-` for stdin, defaults to `dayN` in the input directory
+    #[arg(conflicts_with = "all")]
+    inputs: Vec<PathBuf>,
+    /// Additional input file or directory containing `dayN` files, may be repeated,
+    /// `-` for stdin unless running --all
+    #[arg(short, long)]
+    input: Vec<PathBuf>,
+    /// Run the puzzle examples and show their expected answers
+    #[arg(short, long, conflicts_with_all = ["input", "inputs", "format"])]
+    example: bool,
+    /// Output format of the answers
+    #[arg(short, long, value_enum, default_value_t)]
+    format: Format,
+    /// Number of days solved at the same time with --all, defaults to the number of CPUs
+    #[arg(short, long, requires = "all")]
+    jobs: Option<NonZeroUsize>,
+    /// Answer ledger to check the answers of --all against
+    #[arg(long, default_value = ledger::DEFAULT_PATH, requires = "all")]
+    ledger: PathBuf,
+}
+
+pub fn run(args: RunArgs, input_dir: &Path) -> ExitCode {
+    let selected = match args.days.select() {
+        Ok(s) => s,
+        Err(e) => {
+            eprintln!("{e}");
+            return ExitCode::FAILURE;
+        }
+    };
+    if args.example {
+        return run_examples(&selected, args.part);
+    }
+    if args.days.all {
+        return run_all(&args, &selected, input_dir);
+    }
+    let given: Vec<_> = args.inputs.iter().chain(&args.input).collect();
+    let mut records = Vec::new();
+    let mut ok = true;
+    for day in selected {
+        let paths: Vec<_> = if given.is_empty() {
+            vec![crate::default_input(input_dir, day.day())]
+        } else {
+            given
+                .iter()
+                .map(|p| crate::resolve_input(input_dir, day.day(), p))
+                .collect()
+        };
+        for path in &paths {
+            let input = match crate::load(day, path) {
+                Ok(i) => i,
+                Err(e) => {
+                    eprintln!("{e}");
+                    ok = false;
+                    continue;
+                }
+            };
+            let mut solved = Vec::new();
+            for part in 1..=2 {
+                if args.part.is_some_and(|p| p != part) {
+                    continue;
+                }
+                let start = Instant::now();
+                let answer = match part {
+                    1 => input.part1(),
+                    _ => input.part2(),
+                };
+                solved.push(Record {
+                    day: day.day(),
+                    part,
+                    answer: answer.to_string(),
+                    input: path.display().to_string(),
+                    time_ns: start.elapsed().as_nanos() as u64,
+                });
+            }
+            // Text is printed as soon as it is known, structured formats all at once.
+            if args.format == Format::Text {
+                // Only label the answers when there is more than one input to tell apart.
+                print!("{}", output::render(&solved, Format::Text, paths.len() > 1));
+            } else {
+                records.extend(solved);
+            }
+        }
+    }
+    if args.format != Format::Text {
+        print!("{}", output::render(&records, args.format, true));
+    }
+    if ok {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::FAILURE
+    }
+}
+
+/// Solves all `selected` days in parallel and prints a table of the answers,
+/// checked against the ledger, or the records in a structured format.
+fn run_all(args: &RunArgs, selected: &[&'static dyn DynSolution], input_dir: &Path) -> ExitCode {
+    // Stdin holds the input of one day at most, and can only be read once.
+    if args.input.iter().any(|p| crate::is_stdin(p)) {
+        eprintln!("Inputs cannot be read from stdin with --all");
+        return ExitCode::FAILURE;
+    }
+    let ledger = match Ledger::load(&args.ledger) {
+        Ok(l) => l,
+        Err(e) => {
+            eprintln!("Cannot load {}: {e}", args.ledger.display());
+            return ExitCode::FAILURE;
+        }
+    };
+    let threads = args
+        .jobs
+        .or_else(|| thread::available_parallelism().ok())
+        .map_or(1, NonZeroUsize::get);
+    let parts: Vec<u8> = [1, 2]
+        .into_iter()
+        .filter(|&p| args.part.is_none_or(|part| part == p))
+        .collect();
+
+    let mut table = Table::new(["day", "part", "input", "answer", "time", "status"]);
+    let mut failed = 0;
+    let mut jobs = Vec::new();
+    for &day in selected {
+        let paths: Vec<_> = if args.input.is_empty() {
+            vec![crate::default_input(input_dir, day.day())]
+        } else {
+            let resolve = |p: &PathBuf| crate::resolve_input(input_dir, day.day(), p);
+            args.input.iter().map(resolve).collect()
+        };
+        for path in paths {
+            let key = path.display().to_string();
+            match crate::read_input(&path) {
+                Ok(input) => jobs.push(Job { day, key, input }),
+                Err(e) => {
+                    eprintln!("Day {}: cannot read {key}: {e}", day.day());
+                    table.row([&day.day().to_string(), "-", &key, "-", "-", "error"]);
+                    failed += 1;
+                }
+            }
+        }
+    }
+
+    let start = Instant::now();
+    let finished = parallel::solve_all(&jobs, &parts, threads);
+    let wall = start.elapsed();
+
+    let mut records = Vec::new();
+    let mut busy = Duration::ZERO;
+    for f in finished {
+        let day = f.day.to_string();
+        let solved = match f.parts {
+            Ok(solved) => solved,
+            Err(e) => {
+                eprintln!("{}: {e}", f.key);
+                table.row([&day, "-", &f.key, "-", "-", "error"]);
+                failed += 1;
+                continue;
+            }
+        };
+        for s in solved {
+            busy += s.time;
+            let (answer, status) = match &s.answer {
+                Ok(answer) => {
+                    let answer = answer.to_string();
+                    let status = match ledger.get(f.day, &f.key, s.part) {
+                        Some(expected) if expected == answer => "ok",
+                        Some(_) => "MISMATCH",
+                        None => "missing",
+                    };
+                    (answer, status)
+                }
+                Err(msg) => {
+                    eprintln!("Day {day} part {} panicked: {msg}", s.part);
+                    ("-".to_string(), "PANIC")
+                }
+            };
+            if matches!(status, "MISMATCH" | "PANIC") {
+                failed += 1;
+            }
+            table.row([
+                &day,
+                &s.part.to_string(),
+                &f.key,
+                &answer,
+                &format!("{:?}", s.time),
+                status,
+            ]);
+            if s.answer.is_ok() {
+                records.push(Record {
+                    day: f.day,
+                    part: s.part,
+                    answer,
+                    input: f.key.clone(),
+                    time_ns: s.time.as_nanos() as u64,
+                });
+            }
+        }
+    }
+
+    if args.format == Format::Text {
+        print!("{table}");
+        let plural = if threads == 1 { "" } else { "s" };
+        println!("Total wall time {wall:?} on {threads} thread{plural}, {busy:?} spent solving");
+    } else {
+        print!("{}", output::render(&records, args.format, true));
+    }
+    if failed > 0 {
+        eprintln!("{failed} failed");
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    }
+}
+
+fn run_examples(selected: &[&dyn DynSolution], part: Option<u8>) -> ExitCode {
+    let mut ok = true;
+    for day in selected {
+        let examples = match examples::load(day.day()) {
+            Ok(e) => e,
+            Err(e) => {
+                let dir = examples::dir(day.day());
+                eprintln!("Day {}: cannot load {}: {e}", day.day(), dir.display());
+                ok = false;
+                continue;
+            }
+        };
+        for ex in examples {
+            let input = match day.parse(&ex.input) {
+                Ok(i) => i,
+                Err(e) => {
+                    eprintln!("{}: {}", ex.name, e.diagnostic());
+                    ok = false;
+                    continue;
+                }
+            };
+            for p in 1..=2 {
+                if part.is_some_and(|part| part != p) {
+                    continue;
+                }
+                let answer = match p {
+                    1 => input.part1(),
+                    _ => input.part2(),
+                }
+                .to_string();
+                let mut line = format!("Day {} part {p} ({}): {answer}", day.day(), ex.name);
+                if let Some(expected) = ex.expected.part(p) {
+                    if expected == answer {
+                        line += &format!(" (expected {expected})");
+                    } else {
+                        line += &format!(" (expected {expected}, MISMATCH)");
+                        ok = false;
+                    }
+                }
+                println!("{line}");
+            }
+        }
+    }
+    if ok {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::FAILURE
+    }
+}
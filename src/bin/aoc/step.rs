@@ -0,0 +1,66 @@
+use aoc_2024::days;
+use aoc_2024::stepper::{Reply, Session};
+use clap::Args;
+use std::io::{self, BufRead, Write};
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+
+#[derive(Args, Debug)]
+pub struct StepArgs {
+    /// Day to step through
+    day: u32,
+    /// Input file, defaults to `dayN` in the input directory
+    #[arg(short, long)]
+    input: Option<PathBuf>,
+}
+
+pub fn step(args: StepArgs, input_dir: &Path) -> ExitCode {
+    let Some(day) = days::get(args.day) else {
+        eprintln!("Day {} is not implemented", args.day);
+        return ExitCode::FAILURE;
+    };
+    let path = match &args.input {
+        Some(path) => crate::resolve_input(input_dir, args.day, path),
+        None => crate::default_input(input_dir, args.day),
+    };
+    // Commands come from stdin, so the input cannot.
+    if crate::is_stdin(&path) {
+        eprintln!("The input cannot be read from stdin while stepping");
+        return ExitCode::FAILURE;
+    }
+    let parsed = match crate::load(day, &path) {
+        Ok(p) => p,
+        Err(e) => {
+            eprintln!("{e}");
+            return ExitCode::FAILURE;
+        }
+    };
+    let Some(stepper) = parsed.stepper() else {
+        eprintln!("Day {} cannot be stepped through", args.day);
+        return ExitCode::FAILURE;
+    };
+
+    let mut session = Session::new(stepper);
+    println!("{}", session.view());
+    let mut lines = io::stdin().lock().lines();
+    loop {
+        print!("> ");
+        let _ = io::stdout().flush();
+        let line = match lines.next() {
+            Some(Ok(line)) => line,
+            Some(Err(e)) => {
+                eprintln!("Cannot read command: {e}");
+                return ExitCode::FAILURE;
+            }
+            None => {
+                println!();
+                return ExitCode::SUCCESS;
+            }
+        };
+        match session.execute(&line) {
+            Ok(Reply::Show(out)) => println!("{out}"),
+            Ok(Reply::Quit) => return ExitCode::SUCCESS,
+            Err(e) => eprintln!("{e}"),
+        }
+    }
+}
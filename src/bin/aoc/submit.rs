@@ -0,0 +1,100 @@
+use aoc_2024::client::{self, Client, Config, Ureq, Verdict};
+use aoc_2024::days;
+use aoc_2024::history::{self, History};
+use clap::Args;
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+#[derive(Args, Debug)]
+pub struct SubmitArgs {
+    /// Day to submit
+    day: u32,
+    /// Part to submit
+    #[arg(short, long, value_parser = clap::value_parser!(u8).range(1..=2))]
+    part: u8,
+    /// Answer to submit instead of solving the input
+    #[arg(short, long)]
+    answer: Option<String>,
+    /// Input file to solve, `-` for stdin, defaults to `dayN` in the input directory
+    #[arg(short, long, conflicts_with = "answer")]
+    input: Option<PathBuf>,
+    /// Submission history
+    #[arg(long, default_value = history::DEFAULT_PATH)]
+    history: PathBuf,
+    /// Config file with the session token
+    #[arg(long, default_value = client::DEFAULT_CONFIG)]
+    config: PathBuf,
+}
+
+fn answer(args: &SubmitArgs, input_dir: &Path) -> Result<String, String> {
+    if let Some(answer) = &args.answer {
+        return Ok(answer.clone());
+    }
+    let day = days::get(args.day).ok_or_else(|| format!("Day {} is not implemented", args.day))?;
+    let path = match &args.input {
+        Some(path) => crate::resolve_input(input_dir, args.day, path),
+        None => crate::default_input(input_dir, args.day),
+    };
+    let input = crate::load(day, &path)?;
+    Ok(match args.part {
+        1 => input.part1(),
+        _ => input.part2(),
+    }
+    .to_string())
+}
+
+pub fn submit(args: SubmitArgs, input_dir: &Path) -> ExitCode {
+    let answer = match answer(&args, input_dir) {
+        Ok(a) => a,
+        Err(e) => {
+            eprintln!("{e}");
+            return ExitCode::FAILURE;
+        }
+    };
+    let mut history = match History::load(&args.history) {
+        Ok(h) => h,
+        Err(e) => {
+            eprintln!("Cannot load {}: {e}", args.history.display());
+            return ExitCode::FAILURE;
+        }
+    };
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .expect("Clock is after 1970")
+        .as_secs();
+    if let Err(refusal) = history.check(args.day, args.part, &answer, now) {
+        eprintln!(
+            "Day {} part {}: not submitting {answer}, {refusal}",
+            args.day, args.part
+        );
+        return ExitCode::FAILURE;
+    }
+    let config = match Config::load(&args.config) {
+        Ok(c) => c,
+        Err(e) => {
+            eprintln!("Cannot load {}: {e}", args.config.display());
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let client = Client::new(Ureq::new(), &config);
+    let verdict = match client.submit(args.day, args.part, &answer) {
+        Ok(v) => v,
+        Err(e) => {
+            eprintln!("Day {} part {}: {e}", args.day, args.part);
+            return ExitCode::FAILURE;
+        }
+    };
+    println!("Day {} part {}: {answer} is {verdict}", args.day, args.part);
+    history.record(args.day, args.part, &answer, verdict, now);
+    if let Err(e) = history.save(&args.history) {
+        eprintln!("Cannot write {}: {e}", args.history.display());
+        return ExitCode::FAILURE;
+    }
+    if verdict == Verdict::Correct {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::FAILURE
+    }
+}
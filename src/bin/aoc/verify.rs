@@ -0,0 +1,102 @@
+use crate::Days;
+use aoc_2024::ledger::{self, Ledger};
+use aoc_2024::table::Table;
+use clap::Args;
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+
+#[derive(Args, Debug)]
+pub struct VerifyArgs {
+    #[command(flatten)]
+    days: Days,
+    /// Input file, `-` for stdin, defaults to `dayN` in the input directory
+    #[arg(short, long, conflicts_with = "all")]
+    input: Option<PathBuf>,
+    /// Answer ledger
+    #[arg(long, default_value = ledger::DEFAULT_PATH)]
+    ledger: PathBuf,
+    /// Write answers missing from the ledger into it
+    #[arg(long)]
+    record: bool,
+}
+
+pub fn verify(args: VerifyArgs, input_dir: &Path) -> ExitCode {
+    let selected = match args.days.select() {
+        Ok(s) => s,
+        Err(e) => {
+            eprintln!("{e}");
+            return ExitCode::FAILURE;
+        }
+    };
+    let mut ledger = match Ledger::load(&args.ledger) {
+        Ok(l) => l,
+        Err(e) => {
+            eprintln!("Cannot load {}: {e}", args.ledger.display());
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let mut table = Table::new(["day", "part", "input", "answer", "expected", "status"]);
+    let mut failed = 0;
+    let mut recorded = 0;
+    for day in selected {
+        let path = match &args.input {
+            Some(path) => crate::resolve_input(input_dir, day.day(), path),
+            None => crate::default_input(input_dir, day.day()),
+        };
+        let key = path.display().to_string();
+        let input = match crate::load(day, &path) {
+            Ok(i) => i,
+            Err(e) => {
+                eprintln!("{e}");
+                table.row([&day.day().to_string(), "-", &key, "-", "-", "error"]);
+                failed += 1;
+                continue;
+            }
+        };
+        for part in 1..=2 {
+            let answer = match part {
+                1 => input.part1(),
+                _ => input.part2(),
+            }
+            .to_string();
+            let expected = ledger.get(day.day(), &key, part).map(str::to_string);
+            let status = match &expected {
+                Some(e) if *e == answer => "ok",
+                Some(_) => {
+                    failed += 1;
+                    "FAIL"
+                }
+                None if args.record => {
+                    ledger.set(day.day(), &key, part, answer.clone());
+                    recorded += 1;
+                    "recorded"
+                }
+                None => "missing",
+            };
+            table.row([
+                &day.day().to_string(),
+                &part.to_string(),
+                &key,
+                &answer,
+                expected.as_deref().unwrap_or("-"),
+                status,
+            ]);
+        }
+    }
+    print!("{table}");
+
+    if recorded > 0 {
+        if let Err(e) = ledger.save(&args.ledger) {
+            eprintln!("Cannot write {}: {e}", args.ledger.display());
+            return ExitCode::FAILURE;
+        }
+        println!("Recorded {recorded} answers in {}", args.ledger.display());
+    }
+    if failed > 0 {
+        println!("{failed} failed");
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    }
+}
@@ -0,0 +1,638 @@
+use serde::Deserialize;
+use std::cell::Cell;
+use std::error::Error;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// The puzzle site.
+pub const BASE_URL: &str = "https://adventofcode.com";
+
+/// Puzzle year all days belong to.
+pub const YEAR: u32 = 2024;
+
+/// Environment variable holding the session cookie, overriding the config file.
+pub const SESSION_VAR: &str = "AOC_SESSION";
+
+/// Default location of the config file, relative to the repository root.
+pub const DEFAULT_CONFIG: &str = "aoc.toml";
+
+/// Minimum time between two requests to the site.
+pub const THROTTLE: Duration = Duration::from_secs(5);
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Method {
+    Get,
+    Post,
+}
+
+impl Method {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Method::Get => "GET",
+            Method::Post => "POST",
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Request {
+    pub method: Method,
+    pub url: String,
+    pub headers: Vec<(String, String)>,
+    pub body: Option<String>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Response {
+    pub status: u16,
+    pub body: String,
+}
+
+/// The HTTP layer, so tests can swap the network for something local.
+///
+/// Error statuses are responses, only failing to talk to the server is an error.
+pub trait Http {
+    fn send(&self, request: &Request) -> Result<Response, String>;
+}
+
+/// [`Http`] over the network.
+pub struct Ureq {
+    agent: ureq::Agent,
+}
+
+impl Ureq {
+    pub fn new() -> Self {
+        let agent = ureq::AgentBuilder::new()
+            .timeout(Duration::from_secs(30))
+            .build();
+        Self { agent }
+    }
+}
+
+impl Default for Ureq {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Http for Ureq {
+    fn send(&self, request: &Request) -> Result<Response, String> {
+        let mut req = self.agent.request(request.method.as_str(), &request.url);
+        for (name, value) in &request.headers {
+            req = req.set(name, value);
+        }
+        let result = match &request.body {
+            Some(body) => req.send_string(body),
+            None => req.call(),
+        };
+        let res = match result {
+            Ok(res) | Err(ureq::Error::Status(_, res)) => res,
+            Err(e) => return Err(e.to_string()),
+        };
+        let status = res.status();
+        let body = res.into_string().map_err(|e| e.to_string())?;
+        Ok(Response { status, body })
+    }
+}
+
+#[derive(Debug)]
+pub enum ClientError {
+    /// Neither the environment nor the config file has a session token.
+    NoSession,
+    /// The request did not get a response.
+    Http(String),
+    /// The site answered with an error status.
+    Status(u16, String),
+    /// The answer page did not contain any known verdict.
+    UnknownVerdict(String),
+    Io(io::Error),
+}
+
+impl fmt::Display for ClientError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ClientError::NoSession => write!(
+                f,
+                "no session token, set {SESSION_VAR} or `session` in {DEFAULT_CONFIG}"
+            ),
+            ClientError::Http(e) => write!(f, "request failed: {e}"),
+            ClientError::Status(status, body) => {
+                let reason = body.lines().next().unwrap_or_default();
+                write!(f, "server answered {status}: {reason}")
+            }
+            ClientError::UnknownVerdict(page) => {
+                write!(f, "cannot make sense of the answer page: {page}")
+            }
+            ClientError::Io(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+impl Error for ClientError {}
+
+impl From<io::Error> for ClientError {
+    fn from(e: io::Error) -> Self {
+        ClientError::Io(e)
+    }
+}
+
+/// What the site said about a submitted answer.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Verdict {
+    Correct,
+    TooHigh,
+    TooLow,
+    /// Wrong, without a hint in which direction.
+    Wrong,
+    /// Submitted too soon after the previous answer, with the time left to wait if given.
+    RateLimited(Option<Duration>),
+    /// The part is locked or already solved.
+    WrongLevel,
+}
+
+impl Verdict {
+    /// Finds the verdict in the HTML of an answer page.
+    pub fn parse(page: &str) -> Option<Self> {
+        let text = page.split_whitespace().collect::<Vec<_>>().join(" ");
+        if text.contains("That's the right answer") {
+            Some(Verdict::Correct)
+        } else if text.contains("your answer is too high") {
+            Some(Verdict::TooHigh)
+        } else if text.contains("your answer is too low") {
+            Some(Verdict::TooLow)
+        } else if text.contains("That's not the right answer") {
+            Some(Verdict::Wrong)
+        } else if text.contains("You gave an answer too recently") {
+            Some(Verdict::RateLimited(parse_wait(&text)))
+        } else if text.contains("You don't seem to be solving the right level") {
+            Some(Verdict::WrongLevel)
+        } else {
+            None
+        }
+    }
+}
+
+impl fmt::Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Verdict::Correct => write!(f, "correct"),
+            Verdict::TooHigh => write!(f, "too high"),
+            Verdict::TooLow => write!(f, "too low"),
+            Verdict::Wrong => write!(f, "wrong"),
+            Verdict::RateLimited(Some(wait)) => write!(f, "rate limited for {}s", wait.as_secs()),
+            Verdict::RateLimited(None) => write!(f, "rate limited"),
+            Verdict::WrongLevel => write!(f, "wrong level"),
+        }
+    }
+}
+
+/// Parses the wait time out of "You have 1m 23s left to wait".
+fn parse_wait(text: &str) -> Option<Duration> {
+    let (before, _) = text.split_once(" left to wait")?;
+    let (_, amount) = before.rsplit_once("You have ")?;
+    let mut secs = 0;
+    for part in amount.split_whitespace() {
+        let (n, unit) = [("h", 3600), ("m", 60), ("s", 1)]
+            .into_iter()
+            .find_map(|(suffix, unit)| Some((part.strip_suffix(suffix)?, unit)))?;
+        let n: u64 = n.parse().ok()?;
+        secs = n.checked_mul(unit)?.checked_add(secs)?;
+    }
+    Some(Duration::from_secs(secs))
+}
+
+/// Percent-encodes `s` for a form body.
+fn form_encode(s: &str) -> String {
+    let mut out = String::new();
+    for b in s.bytes() {
+        if b.is_ascii_alphanumeric() || b"-._~".contains(&b) {
+            out.push(b as char);
+        } else {
+            out += &format!("%{b:02X}");
+        }
+    }
+    out
+}
+
+/// Settings read from the config file.
+///
+/// ```toml
+/// session = "53616c7465645f5f..."
+/// contact = "someone@example.com"
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Config {
+    /// Value of the `session` cookie of a logged in browser.
+    pub session: Option<String>,
+    /// Added to the User-Agent, so the site operators know whom to reach.
+    pub contact: Option<String>,
+}
+
+impl Config {
+    /// Reads the config at `path`, returning an empty config if the file does not exist.
+    ///
+    /// The session token from [`SESSION_VAR`] takes precedence over the file.
+    pub fn load(path: impl AsRef<Path>) -> io::Result<Self> {
+        let mut config: Config = match fs::read_to_string(path) {
+            Ok(f) => {
+                toml::from_str(&f).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?
+            }
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Self::default(),
+            Err(e) => return Err(e),
+        };
+        if let Ok(session) = std::env::var(SESSION_VAR) {
+            config.session = Some(session);
+        }
+        Ok(config)
+    }
+}
+
+/// Talks to the puzzle site, at most once per throttle interval.
+pub struct Client<H> {
+    http: H,
+    base_url: String,
+    session: Option<String>,
+    user_agent: String,
+    throttle: Duration,
+    last_request: Cell<Option<Instant>>,
+}
+
+impl<H: Http> Client<H> {
+    pub fn new(http: H, config: &Config) -> Self {
+        let mut user_agent =
+            concat!(env!("CARGO_PKG_NAME"), "/", env!("CARGO_PKG_VERSION")).to_string();
+        if let Some(contact) = &config.contact {
+            user_agent += &format!(" ({contact})");
+        }
+        Self {
+            http,
+            base_url: BASE_URL.to_string(),
+            session: config.session.clone(),
+            user_agent,
+            throttle: THROTTLE,
+            last_request: Cell::new(None),
+        }
+    }
+
+    /// Sends requests to `base_url` instead of the real site.
+    pub fn with_base_url(mut self, base_url: impl Into<String>) -> Self {
+        self.base_url = base_url.into();
+        self
+    }
+
+    pub fn with_throttle(mut self, throttle: Duration) -> Self {
+        self.throttle = throttle;
+        self
+    }
+
+    pub fn user_agent(&self) -> &str {
+        &self.user_agent
+    }
+
+    fn day_url(&self, day: u32) -> String {
+        format!("{}/{YEAR}/day/{day}", self.base_url)
+    }
+
+    /// Sends an authenticated request, waiting out the throttle first.
+    fn send(
+        &self,
+        method: Method,
+        url: String,
+        body: Option<String>,
+    ) -> Result<String, ClientError> {
+        let session = self.session.as_ref().ok_or(ClientError::NoSession)?;
+        if let Some(last) = self.last_request.get() {
+            let elapsed = last.elapsed();
+            if elapsed < self.throttle {
+                thread::sleep(self.throttle - elapsed);
+            }
+        }
+        let mut headers = vec![
+            ("User-Agent".to_string(), self.user_agent.clone()),
+            ("Cookie".to_string(), format!("session={session}")),
+        ];
+        if body.is_some() {
+            headers.push((
+                "Content-Type".to_string(),
+                "application/x-www-form-urlencoded".to_string(),
+            ));
+        }
+        let request = Request {
+            method,
+            url,
+            headers,
+            body,
+        };
+        let response = self.http.send(&request);
+        self.last_request.set(Some(Instant::now()));
+        let response = response.map_err(ClientError::Http)?;
+        if response.status != 200 {
+            return Err(ClientError::Status(response.status, response.body));
+        }
+        Ok(response.body)
+    }
+
+    /// Downloads the input of `day`.
+    pub fn input(&self, day: u32) -> Result<String, ClientError> {
+        self.send(Method::Get, format!("{}/input", self.day_url(day)), None)
+    }
+
+    /// Downloads the input of `day` into `dir/dayN`, unless it is already there.
+    ///
+    /// Returns the path and whether it was downloaded. Empty files, as left by
+    /// scaffolding, do not count as cached.
+    pub fn fetch_input(&self, day: u32, dir: &Path) -> Result<(PathBuf, bool), ClientError> {
+        let path = dir.join(format!("day{day}"));
+        if fs::metadata(&path).is_ok_and(|m| m.len() > 0) {
+            return Ok((path, false));
+        }
+        let input = self.input(day)?;
+        fs::create_dir_all(dir)?;
+        // Write to a temporary file first, so an interrupted download is not
+        // mistaken for a cached one.
+        let tmp = dir.join(format!(".day{day}.tmp"));
+        fs::write(&tmp, input)?;
+        fs::rename(&tmp, &path)?;
+        Ok((path, true))
+    }
+
+    /// Submits `answer` to `part` of `day`.
+    pub fn submit(&self, day: u32, part: u8, answer: &str) -> Result<Verdict, ClientError> {
+        let body = format!("level={part}&answer={}", form_encode(answer));
+        let page = self.send(
+            Method::Post,
+            format!("{}/answer", self.day_url(day)),
+            Some(body),
+        )?;
+        Verdict::parse(&page).ok_or_else(|| {
+            // Keep the error readable, the page is mostly markup.
+            let start = page.find("<main>").unwrap_or(0);
+            ClientError::UnknownVerdict(page[start..].chars().take(200).collect())
+        })
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod mock {
+    use super::*;
+    use std::io::{BufRead, BufReader, Read, Write};
+    use std::net::{TcpListener, TcpStream};
+    use std::sync::{Arc, Mutex};
+
+    /// A request as received by the [`MockServer`].
+    #[derive(Clone, Debug)]
+    pub struct Received {
+        pub method: String,
+        pub path: String,
+        pub headers: Vec<(String, String)>,
+        pub body: String,
+    }
+
+    impl Received {
+        pub fn header(&self, name: &str) -> Option<&str> {
+            self.headers
+                .iter()
+                .find(|(n, _)| n.eq_ignore_ascii_case(name))
+                .map(|(_, v)| v.as_str())
+        }
+    }
+
+    type Handler = dyn Fn(&Received) -> (u16, String) + Send + Sync;
+
+    /// A minimal HTTP/1.1 server on localhost answering every request with `handler`.
+    pub struct MockServer {
+        pub url: String,
+        received: Arc<Mutex<Vec<Received>>>,
+    }
+
+    impl MockServer {
+        pub fn start(handler: impl Fn(&Received) -> (u16, String) + Send + Sync + 'static) -> Self {
+            let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+            let url = format!("http://{}", listener.local_addr().unwrap());
+            let received = Arc::new(Mutex::new(Vec::new()));
+            let log = received.clone();
+            let handler: Arc<Handler> = Arc::new(handler);
+            thread::spawn(move || {
+                for stream in listener.incoming() {
+                    let Ok(stream) = stream else { continue };
+                    if let Some(req) = serve(stream, handler.as_ref()) {
+                        log.lock().unwrap().push(req);
+                    }
+                }
+            });
+            Self { url, received }
+        }
+
+        pub fn received(&self) -> Vec<Received> {
+            self.received.lock().unwrap().clone()
+        }
+    }
+
+    fn serve(stream: TcpStream, handler: &Handler) -> Option<Received> {
+        let mut reader = BufReader::new(stream.try_clone().ok()?);
+        let mut line = String::new();
+        reader.read_line(&mut line).ok()?;
+        let mut parts = line.split_whitespace();
+        let method = parts.next()?.to_string();
+        let path = parts.next()?.to_string();
+        let mut headers = Vec::new();
+        loop {
+            let mut line = String::new();
+            reader.read_line(&mut line).ok()?;
+            let line = line.trim_end();
+            if line.is_empty() {
+                break;
+            }
+            let (name, value) = line.split_once(':')?;
+            headers.push((name.to_string(), value.trim().to_string()));
+        }
+        let len = headers
+            .iter()
+            .find(|(n, _)| n.eq_ignore_ascii_case("content-length"))
+            .and_then(|(_, v)| v.parse().ok())
+            .unwrap_or(0);
+        let mut body = vec![0; len];
+        reader.read_exact(&mut body).ok()?;
+        let req = Received {
+            method,
+            path,
+            headers,
+            body: String::from_utf8_lossy(&body).into_owned(),
+        };
+        let (status, body) = handler(&req);
+        let mut stream = stream;
+        write!(
+            stream,
+            "HTTP/1.1 {status} Mock\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+            body.len()
+        )
+        .ok()?;
+        Some(req)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::mock::MockServer;
+    use super::*;
+    use std::cell::RefCell;
+
+    fn config() -> Config {
+        Config {
+            session: Some("secret".to_string()),
+            contact: Some("me@example.com".to_string()),
+        }
+    }
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("aoc-client-{name}-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        dir
+    }
+
+    #[test]
+    fn test_fetch_from_mock_server() {
+        let server = MockServer::start(|req| match req.path.as_str() {
+            "/2024/day/3/input" => (200, "mul(2,4)\n".to_string()),
+            _ => (404, "Not Found".to_string()),
+        });
+        let client = Client::new(Ureq::new(), &config())
+            .with_base_url(&server.url)
+            .with_throttle(Duration::ZERO);
+        let dir = temp_dir("fetch");
+
+        let (path, downloaded) = client.fetch_input(3, &dir).unwrap();
+        assert!(downloaded);
+        assert_eq!(fs::read_to_string(&path).unwrap(), "mul(2,4)\n");
+        let (_, downloaded) = client.fetch_input(3, &dir).unwrap();
+        assert!(!downloaded);
+
+        let err = client.fetch_input(4, &dir).unwrap_err();
+        assert!(matches!(err, ClientError::Status(404, _)));
+        assert!(!dir.join("day4").exists());
+
+        let received = server.received();
+        assert_eq!(received.len(), 2);
+        assert_eq!(received[0].method, "GET");
+        assert_eq!(received[0].body, "");
+        assert_eq!(received[0].header("cookie"), Some("session=secret"));
+        let agent = received[0].header("user-agent").unwrap();
+        assert!(agent.starts_with("aoc_2024/"));
+        assert!(agent.contains("me@example.com"));
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    /// Answers every request locally, remembering when it was sent.
+    struct Recorder(RefCell<Vec<Instant>>);
+
+    impl Http for Recorder {
+        fn send(&self, _request: &Request) -> Result<Response, String> {
+            self.0.borrow_mut().push(Instant::now());
+            Ok(Response {
+                status: 200,
+                body: "1 2\n".to_string(),
+            })
+        }
+    }
+
+    #[test]
+    fn test_throttle() {
+        let throttle = Duration::from_millis(50);
+        let client =
+            Client::new(Recorder(RefCell::new(Vec::new())), &config()).with_throttle(throttle);
+        for day in 1..=3 {
+            client.input(day).unwrap();
+        }
+        let sent = client.http.0.borrow();
+        assert!(sent.windows(2).all(|w| w[1] - w[0] >= throttle));
+    }
+
+    fn answer_page(message: &str) -> String {
+        format!("<html><body><main>\n<article><p>{message}</p></article>\n</main></body></html>")
+    }
+
+    #[test]
+    fn test_verdict() {
+        let cases = [
+            ("That's the right answer!  You are one gold star closer.", Verdict::Correct),
+            (
+                "That's not the right answer; your answer is too high.  If you're stuck...",
+                Verdict::TooHigh,
+            ),
+            ("That's not the right answer; your answer is too low.", Verdict::TooLow),
+            ("That's not the right answer.  If you're stuck...", Verdict::Wrong),
+            (
+                "You gave an answer too recently; you have to wait after submitting an answer before trying again.  You have 1m 23s left to wait.",
+                Verdict::RateLimited(Some(Duration::from_secs(83))),
+            ),
+            (
+                "You gave an answer too recently.  You have 45s left to wait.",
+                Verdict::RateLimited(Some(Duration::from_secs(45))),
+            ),
+            (
+                "You don't seem to be solving the right level.  Did you already complete it?",
+                Verdict::WrongLevel,
+            ),
+        ];
+        for (message, verdict) in cases {
+            assert_eq!(
+                Verdict::parse(&answer_page(message)),
+                Some(verdict),
+                "{message}"
+            );
+        }
+        assert_eq!(Verdict::parse("<html></html>"), None);
+    }
+
+    #[test]
+    fn test_parse_wait() {
+        let wait = |amount: &str| parse_wait(&format!("You have {amount} left to wait."));
+        assert_eq!(wait("2h 1m 5s"), Some(Duration::from_secs(7265)));
+        assert_eq!(wait("5é"), None);
+        assert_eq!(wait("é"), None);
+        assert_eq!(wait("5"), None);
+        assert_eq!(wait("18446744073709551615h"), None);
+        assert_eq!(parse_wait("Please wait."), None);
+    }
+
+    #[test]
+    fn test_submit_to_mock_server() {
+        let server = MockServer::start(|req| {
+            let message = match req.body.as_str() {
+                "level=1&answer=42" => "That's the right answer!",
+                "level=2&answer=a%20b" => "That's not the right answer.",
+                _ => "Nothing to see here",
+            };
+            (200, answer_page(message))
+        });
+        let client = Client::new(Ureq::new(), &config())
+            .with_base_url(&server.url)
+            .with_throttle(Duration::ZERO);
+        assert_eq!(client.submit(7, 1, "42").unwrap(), Verdict::Correct);
+        assert_eq!(client.submit(7, 2, "a b").unwrap(), Verdict::Wrong);
+        assert!(matches!(
+            client.submit(7, 2, "1"),
+            Err(ClientError::UnknownVerdict(_))
+        ));
+
+        let received = server.received();
+        assert_eq!(received[0].method, "POST");
+        assert_eq!(received[0].path, "/2024/day/7/answer");
+        assert_eq!(
+            received[0].header("content-type"),
+            Some("application/x-www-form-urlencoded")
+        );
+    }
+
+    #[test]
+    fn test_no_session() {
+        let client = Client::new(Recorder(RefCell::new(Vec::new())), &Config::default());
+        assert!(matches!(client.input(1), Err(ClientError::NoSession)));
+        assert!(client.http.0.borrow().is_empty());
+    }
+}
@@ -0,0 +1,194 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// Where the sequence `start, f(start), f(f(start)), ...` starts repeating.
+///
+/// The state after `start` steps is the first one on the cycle, and it comes
+/// back every `length` steps.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Cycle {
+    pub start: usize,
+    pub length: usize,
+}
+
+impl Cycle {
+    /// The smallest number of steps leading to the same state as `n` steps.
+    pub fn reduce(&self, n: u64) -> u64 {
+        let (start, length) = (self.start as u64, self.length as u64);
+        if n < start {
+            n
+        } else {
+            start + (n - start) % length
+        }
+    }
+}
+
+/// Finds the cycle of the sequence of states starting at `start`, where `f`
+/// returns the next state or `None` when the sequence ends.
+///
+/// Returns `None` if the sequence ends. Uses Floyd's tortoise and hare, which
+/// keeps only two states around.
+pub fn floyd<S: Clone + PartialEq>(start: S, f: impl Fn(&S) -> Option<S>) -> Option<Cycle> {
+    let mut tortoise = f(&start)?;
+    let mut hare = f(&tortoise)?;
+    while tortoise != hare {
+        tortoise = f(&tortoise)?;
+        hare = f(&f(&hare)?)?;
+    }
+
+    let mut cycle_start = 0;
+    tortoise = start;
+    while tortoise != hare {
+        tortoise = f(&tortoise)?;
+        hare = f(&hare)?;
+        cycle_start += 1;
+    }
+
+    let mut length = 1;
+    hare = f(&tortoise)?;
+    while tortoise != hare {
+        hare = f(&hare)?;
+        length += 1;
+    }
+    Some(Cycle {
+        start: cycle_start,
+        length,
+    })
+}
+
+/// Like [`floyd`], using Brent's algorithm, which usually needs fewer steps.
+pub fn brent<S: Clone + PartialEq>(start: S, f: impl Fn(&S) -> Option<S>) -> Option<Cycle> {
+    let mut power = 1;
+    let mut length = 1;
+    let mut tortoise = start.clone();
+    let mut hare = f(&start)?;
+    while tortoise != hare {
+        if power == length {
+            tortoise = hare.clone();
+            power *= 2;
+            length = 0;
+        }
+        hare = f(&hare)?;
+        length += 1;
+    }
+
+    let mut tortoise = start.clone();
+    let mut hare = start;
+    for _ in 0..length {
+        hare = f(&hare)?;
+    }
+    let mut cycle_start = 0;
+    while tortoise != hare {
+        tortoise = f(&tortoise)?;
+        hare = f(&hare)?;
+        cycle_start += 1;
+    }
+    Some(Cycle {
+        start: cycle_start,
+        length,
+    })
+}
+
+/// Like [`floyd`], remembering every state seen, which takes the fewest steps
+/// but the most memory.
+pub fn hashed<S: Clone + Eq + Hash>(start: S, f: impl Fn(&S) -> Option<S>) -> Option<Cycle> {
+    let mut seen = HashMap::new();
+    let mut state = start;
+    for i in 0.. {
+        if let Some(&first) = seen.get(&state) {
+            return Some(Cycle {
+                start: first,
+                length: i - first,
+            });
+        }
+        let next = f(&state)?;
+        seen.insert(state, i);
+        state = next;
+    }
+    unreachable!()
+}
+
+/// The state after `n` steps from `start`, or `None` if the sequence ends before.
+///
+/// Takes a shortcut through the cycle of the sequence, so `n` can be far larger
+/// than the number of distinct states.
+pub fn nth<S: Clone + PartialEq>(start: S, f: impl Fn(&S) -> Option<S>, n: u64) -> Option<S> {
+    let steps = match brent(start.clone(), &f) {
+        Some(cycle) => cycle.reduce(n),
+        // The sequence ends, so walking it takes at most as long as the search.
+        None => n,
+    };
+    (0..steps).try_fold(start, |state, _| f(&state))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    /// A pseudo random walk over 0..1000, bound to run into a cycle.
+    fn next(x: &u64) -> Option<u64> {
+        Some((x * x + 7) % 1000)
+    }
+
+    /// Counts down and ends at zero.
+    fn countdown(x: &u64) -> Option<u64> {
+        x.checked_sub(1)
+    }
+
+    fn naive(start: u64) -> Cycle {
+        let mut states = vec![start];
+        loop {
+            let next = next(states.last().unwrap()).unwrap();
+            if let Some(first) = states.iter().position(|&s| s == next) {
+                return Cycle {
+                    start: first,
+                    length: states.len() - first,
+                };
+            }
+            states.push(next);
+        }
+    }
+
+    #[test]
+    fn test_detectors_agree() {
+        for start in 0..200 {
+            let expected = naive(start);
+            assert_eq!(floyd(start, next), Some(expected), "floyd from {start}");
+            assert_eq!(brent(start, next), Some(expected), "brent from {start}");
+            assert_eq!(hashed(start, next), Some(expected), "hashed from {start}");
+        }
+    }
+
+    #[test]
+    fn test_fixed_point() {
+        let cycle = Cycle {
+            start: 0,
+            length: 1,
+        };
+        assert_eq!(floyd(5, |&x| Some(x)), Some(cycle));
+        assert_eq!(brent(5, |&x| Some(x)), Some(cycle));
+        assert_eq!(hashed(5, |&x| Some(x)), Some(cycle));
+    }
+
+    #[test]
+    fn test_sequence_ends() {
+        assert_eq!(floyd(10, countdown), None);
+        assert_eq!(brent(10, countdown), None);
+        assert_eq!(hashed(10, countdown), None);
+        assert_eq!(nth(10, countdown, 4), Some(6));
+        assert_eq!(nth(10, countdown, 10), Some(0));
+        assert_eq!(nth(10, countdown, 1_000_000_000_000_000), None);
+    }
+
+    #[test]
+    fn test_nth() {
+        let walk = |start, n| (0..n).fold(start, |x, _| next(&x).unwrap());
+        for n in [0, 1, 5, 50, 999, 12345] {
+            assert_eq!(nth(3, next, n), Some(walk(3, n)));
+        }
+        let cycle = naive(3);
+        let big = 1_000_000_000_000_000;
+        assert_eq!(nth(3, next, big), Some(walk(3, cycle.reduce(big))));
+        assert_eq!(nth(3, next, big + cycle.length as u64), nth(3, next, big));
+    }
+}
@@ -0,0 +1,322 @@
+use super::parse_line;
+use crate::parse::Line;
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+use std::fs::{self, File};
+use std::io::{self, BufRead, BufReader, BufWriter, Read, Write};
+use std::iter::zip;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// Smallest number of IDs per sorted run.
+const MIN_RUN: usize = 1024;
+
+/// Size in bytes of an ID in a run file.
+const ID_SIZE: usize = size_of::<u64>();
+
+/// Smallest memory budget [`part1_external`] accepts, enough for one run of each list.
+pub const MIN_BUDGET: usize = 2 * MIN_RUN * ID_SIZE;
+
+/// Most runs merged at once, so the open files stay well below common limits.
+const FAN_IN: usize = 64;
+
+/// Tells apart the run files of concurrent sorts within this process.
+static SORTS: AtomicUsize = AtomicUsize::new(0);
+
+/// Result of [`part1_external`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct External {
+    pub distance: u64,
+    /// Number of sorted runs each list was split into, 0 if it fit into memory.
+    pub runs: usize,
+}
+
+/// Sorted runs of one list, written to temporary files that are removed on drop.
+struct Runs {
+    dir: PathBuf,
+    name: String,
+    /// Number of run files created so far, including merged away ones.
+    created: usize,
+    paths: Vec<PathBuf>,
+}
+
+impl Runs {
+    fn new(dir: &Path, name: String) -> Self {
+        Self {
+            dir: dir.to_path_buf(),
+            name,
+            created: 0,
+            paths: Vec::new(),
+        }
+    }
+
+    /// Creates the file of a new run at the end of the runs.
+    fn create(&mut self) -> io::Result<File> {
+        let path = self.dir.join(format!("{}-{}", self.name, self.created));
+        self.created += 1;
+        // Remember the file first, so it is cleaned up even if writing fails.
+        self.paths.push(path.clone());
+        File::create(&path)
+    }
+
+    /// Sorts `ids` into a new run and empties them.
+    fn spill(&mut self, ids: &mut Vec<u64>) -> io::Result<()> {
+        ids.sort_unstable();
+        let mut out = BufWriter::new(self.create()?);
+        for id in ids.drain(..) {
+            out.write_all(&id.to_le_bytes())?;
+        }
+        out.flush()
+    }
+
+    /// Merges the oldest runs into new ones until at most [`FAN_IN`] are left,
+    /// using about `budget` bytes of buffers.
+    fn reduce(&mut self, budget: usize) -> io::Result<()> {
+        let buffer = (budget / (FAN_IN + 1)).max(ID_SIZE);
+        while self.paths.len() > FAN_IN {
+            // Merge only as many runs as it takes to get down to FAN_IN.
+            let merged = FAN_IN.min(self.paths.len() - FAN_IN + 1);
+            let mut out = BufWriter::with_capacity(buffer, self.create()?);
+            for id in merge(&self.paths[..merged], buffer)? {
+                out.write_all(&id?.to_le_bytes())?;
+            }
+            out.flush()?;
+            for path in self.paths.drain(..merged) {
+                fs::remove_file(path)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// All IDs of all runs in ascending order, reading each run through a
+    /// buffer of `buffer` bytes.
+    fn merge(&self, buffer: usize) -> io::Result<Merge> {
+        merge(&self.paths, buffer)
+    }
+}
+
+impl Drop for Runs {
+    fn drop(&mut self) {
+        for path in &self.paths {
+            let _ = fs::remove_file(path);
+        }
+    }
+}
+
+fn read_id(reader: &mut impl Read) -> io::Result<Option<u64>> {
+    let mut bytes = [0; ID_SIZE];
+    match reader.read_exact(&mut bytes) {
+        Ok(()) => Ok(Some(u64::from_le_bytes(bytes))),
+        Err(e) if e.kind() == io::ErrorKind::UnexpectedEof => Ok(None),
+        Err(e) => Err(e),
+    }
+}
+
+/// All IDs of the runs in `paths` in ascending order, reading each run
+/// through a buffer of `buffer` bytes.
+fn merge(paths: &[PathBuf], buffer: usize) -> io::Result<Merge> {
+    let mut readers = Vec::with_capacity(paths.len());
+    let mut heap = BinaryHeap::with_capacity(paths.len());
+    for (i, path) in paths.iter().enumerate() {
+        let mut reader = BufReader::with_capacity(buffer, File::open(path)?);
+        if let Some(id) = read_id(&mut reader)? {
+            heap.push(Reverse((id, i)));
+        }
+        readers.push(reader);
+    }
+    Ok(Merge { readers, heap })
+}
+
+/// K-way merge of sorted runs, keeping only the smallest unread ID of each run.
+struct Merge {
+    readers: Vec<BufReader<File>>,
+    heap: BinaryHeap<Reverse<(u64, usize)>>,
+}
+
+impl Iterator for Merge {
+    type Item = io::Result<u64>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let Reverse((id, i)) = self.heap.pop()?;
+        match read_id(&mut self.readers[i]) {
+            Ok(Some(next)) => self.heap.push(Reverse((next, i))),
+            Ok(None) => {}
+            Err(e) => return Some(Err(e)),
+        }
+        Some(Ok(id))
+    }
+}
+
+/// Part 1 on the location lists read from `input`, holding at most about
+/// `budget` bytes of IDs in memory.
+///
+/// Lists that do not fit are split into sorted runs in `tmp_dir`. Runs are
+/// merged into longer ones, at most [`FAN_IN`] at a time, until few enough are
+/// left to merge both lists while summing up the distances. Fails with
+/// [`io::ErrorKind::InvalidInput`] for budgets below [`MIN_BUDGET`].
+pub fn part1_external(input: impl BufRead, budget: usize, tmp_dir: &Path) -> io::Result<External> {
+    if budget < MIN_BUDGET {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("A memory budget of {budget} bytes is below the minimum of {MIN_BUDGET}"),
+        ));
+    }
+    let capacity = budget / (2 * ID_SIZE);
+    let name = format!(
+        "aoc-day1-{}-{}",
+        std::process::id(),
+        SORTS.fetch_add(1, Ordering::Relaxed)
+    );
+    let mut left_runs = Runs::new(tmp_dir, format!("{name}-left"));
+    let mut right_runs = Runs::new(tmp_dir, format!("{name}-right"));
+    let mut left = Vec::with_capacity(capacity);
+    let mut right = Vec::with_capacity(capacity);
+
+    for (number, text) in input.lines().enumerate() {
+        let text = text?;
+        let line = Line {
+            input: &text,
+            text: &text,
+        };
+        let (l, r) = parse_line(line).map_err(|mut e| {
+            e.line = number + 1;
+            io::Error::new(io::ErrorKind::InvalidData, e.diagnostic())
+        })?;
+        left.push(l);
+        right.push(r);
+        if left.len() == capacity {
+            left_runs.spill(&mut left)?;
+            right_runs.spill(&mut right)?;
+        }
+    }
+
+    if left_runs.paths.is_empty() {
+        left.sort_unstable();
+        right.sort_unstable();
+        let distance = zip(left, right).map(|(l, r)| l.abs_diff(r)).sum();
+        return Ok(External { distance, runs: 0 });
+    }
+    if !left.is_empty() {
+        left_runs.spill(&mut left)?;
+        right_runs.spill(&mut right)?;
+    }
+    drop((left, right));
+
+    let runs = left_runs.paths.len();
+    left_runs.reduce(budget)?;
+    right_runs.reduce(budget)?;
+    let buffer = (budget / (2 * left_runs.paths.len())).max(ID_SIZE);
+    let mut distance = 0;
+    // Both lists have an ID on every line, so they have the same length.
+    for (l, r) in zip(left_runs.merge(buffer)?, right_runs.merge(buffer)?) {
+        distance += l?.abs_diff(r?);
+    }
+    Ok(External { distance, runs })
+}
+
+/// Parses a size in bytes with an optional `K`, `M` or `G` suffix for powers of 1024,
+/// which may be followed by `B` or `iB`.
+pub fn parse_size(s: &str) -> Result<usize, String> {
+    let err = || format!("{s} is not a size like 512K, 64MB or 2GiB");
+    let s = s.trim();
+    let (digits, unit) = s.split_at(s.find(|c: char| !c.is_ascii_digit()).unwrap_or(s.len()));
+    let mut unit = unit.trim_start().chars();
+    let shift = match unit.next().map(|c| c.to_ascii_uppercase()) {
+        None | Some('B') if unit.as_str().is_empty() => 0,
+        Some('K') => 10,
+        Some('M') => 20,
+        Some('G') => 30,
+        _ => return Err(err()),
+    };
+    if shift > 0 && !matches!(unit.as_str(), "" | "B" | "iB") {
+        return Err(err());
+    }
+    digits
+        .parse::<usize>()
+        .ok()
+        .and_then(|n| n.checked_mul(1 << shift))
+        .ok_or_else(err)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::days::day1::{part1, Day1};
+    use crate::rng::Rng;
+    use crate::solution::Solution;
+
+    fn tmp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("aoc-day1-{name}-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn test_matches_in_memory() {
+        let dir = tmp_dir("in-memory");
+        for seed in 0..5 {
+            let input = Day1::generate(&mut Rng::new(seed), 5000).unwrap();
+            let (mut left, mut right) = Day1::parse(&input).unwrap();
+            let expected = part1(&mut left, &mut right);
+            let small = part1_external(input.as_bytes(), MIN_BUDGET, &dir).unwrap();
+            assert_eq!(small.distance, expected);
+            assert_eq!(small.runs, 5);
+            let large = part1_external(input.as_bytes(), 1 << 20, &dir).unwrap();
+            assert_eq!(
+                large,
+                External {
+                    distance: expected,
+                    runs: 0
+                }
+            );
+        }
+        // Every run file was cleaned up.
+        assert_eq!(fs::read_dir(&dir).unwrap().count(), 0);
+        fs::remove_dir(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_multi_pass_merge() {
+        let dir = tmp_dir("multi-pass");
+        // More runs than can be merged at once.
+        let input = Day1::generate(&mut Rng::new(7), 100 * MIN_RUN).unwrap();
+        let (mut left, mut right) = Day1::parse(&input).unwrap();
+        let expected = part1(&mut left, &mut right);
+        let res = part1_external(input.as_bytes(), MIN_BUDGET, &dir).unwrap();
+        assert_eq!(res.distance, expected);
+        assert_eq!(res.runs, 100);
+        assert_eq!(fs::read_dir(&dir).unwrap().count(), 0);
+        fs::remove_dir(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_budget_too_small() {
+        let err = part1_external("3   4\n".as_bytes(), MIN_BUDGET - 1, Path::new(".")).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let input = "3   4\n4   3\n2   x\n";
+        let err = part1_external(input.as_bytes(), MIN_BUDGET, Path::new(".")).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+        assert!(err.to_string().starts_with("line 3, column 5:"));
+    }
+
+    #[test]
+    fn test_parse_size() {
+        assert_eq!(parse_size("1000"), Ok(1000));
+        assert_eq!(parse_size("4k"), Ok(4096));
+        assert_eq!(parse_size("64M"), Ok(64 << 20));
+        assert_eq!(parse_size("2GiB"), Ok(2 << 30));
+        assert_eq!(parse_size("8 KB"), Ok(8 << 10));
+        assert_eq!(parse_size("512B"), Ok(512));
+        assert!(parse_size("lots").is_err());
+        assert!(parse_size("M").is_err());
+        assert!(parse_size("1I").is_err());
+        assert!(parse_size("5BBB").is_err());
+        assert!(parse_size("64MIB").is_err());
+        assert!(parse_size("2GB B").is_err());
+        assert!(parse_size("-1K").is_err());
+    }
+}
@@ -0,0 +1,550 @@
+use crate::rng::Rng;
+use std::collections::HashMap;
+use std::fmt;
+
+/// Index of a missing child in [`MergedLists`].
+const NIL: usize = usize::MAX;
+
+/// Which of the two lists to edit.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Side {
+    Left,
+    Right,
+}
+
+impl Side {
+    /// +1 for left and -1 for right IDs, so that running totals compare the lists.
+    fn weight(self) -> i64 {
+        match self {
+            Side::Left => 1,
+            Side::Right => -1,
+        }
+    }
+}
+
+impl fmt::Display for Side {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Side::Left => write!(f, "left"),
+            Side::Right => write!(f, "right"),
+        }
+    }
+}
+
+/// Aggregates over the IDs of a subtree of [`MergedLists`], in order.
+///
+/// The balance after an ID is the number of left minus the number of right IDs
+/// up to and including it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct Summary {
+    /// Number of left and right IDs.
+    count: [usize; 2],
+    /// Sum of the left and the right IDs.
+    sum: [u128; 2],
+    /// Smallest and largest ID.
+    first: u64,
+    last: u64,
+    /// Lowest and highest balance after any ID, counting from 0.
+    low: i64,
+    high: i64,
+    /// Sum over consecutive IDs of the gap between them times the balance in it.
+    area: i128,
+}
+
+impl Summary {
+    fn leaf(id: u64, side: Side) -> Self {
+        let mut count = [0; 2];
+        let mut sum = [0; 2];
+        count[side as usize] = 1;
+        sum[side as usize] = id as u128;
+        Self {
+            count,
+            sum,
+            first: id,
+            last: id,
+            low: side.weight(),
+            high: side.weight(),
+            area: 0,
+        }
+    }
+
+    fn balance(&self) -> i64 {
+        self.count[0] as i64 - self.count[1] as i64
+    }
+
+    /// The IDs of `self` followed by those of `next`.
+    fn then(&self, next: &Summary) -> Summary {
+        let balance = self.balance();
+        Summary {
+            count: [self.count[0] + next.count[0], self.count[1] + next.count[1]],
+            sum: [self.sum[0] + next.sum[0], self.sum[1] + next.sum[1]],
+            first: self.first,
+            last: next.last,
+            low: self.low.min(balance + next.low),
+            high: self.high.max(balance + next.high),
+            // The gap between the two and all of `next` are shifted by `balance`.
+            area: self.area + next.area + balance as i128 * (next.last - self.last) as i128,
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+struct Node {
+    id: u64,
+    side: Side,
+    priority: u64,
+    left: usize,
+    right: usize,
+    summary: Summary,
+}
+
+/// Both location lists in one treap ordered by ID, each node an ID of one side.
+///
+/// Children are indices into `nodes`. Every node keeps the [`Summary`] of its
+/// subtree, so that counts and sums of the smallest IDs of a side take
+/// O(log n) expected time.
+#[derive(Clone, Debug)]
+struct MergedLists {
+    nodes: Vec<Node>,
+    /// Indices of removed nodes, reused by the next inserts.
+    free: Vec<usize>,
+    root: usize,
+    rng: Rng,
+}
+
+impl Default for MergedLists {
+    fn default() -> Self {
+        Self {
+            nodes: Vec::new(),
+            free: Vec::new(),
+            root: NIL,
+            rng: Rng::new(0),
+        }
+    }
+}
+
+impl MergedLists {
+    fn summary(&self, t: usize) -> Option<&Summary> {
+        (t != NIL).then(|| &self.nodes[t].summary)
+    }
+
+    fn update(&mut self, t: usize) {
+        let Node {
+            id,
+            side,
+            left,
+            right,
+            ..
+        } = self.nodes[t];
+        let mut summary = Summary::leaf(id, side);
+        if let Some(l) = self.summary(left) {
+            summary = l.then(&summary);
+        }
+        if let Some(r) = self.summary(right) {
+            summary = summary.then(r);
+        }
+        self.nodes[t].summary = summary;
+    }
+
+    /// Splits the tree at `t` into the IDs for which `below` holds and the rest.
+    fn split(&mut self, t: usize, below: &impl Fn((u64, Side)) -> bool) -> (usize, usize) {
+        if t == NIL {
+            return (NIL, NIL);
+        }
+        if below((self.nodes[t].id, self.nodes[t].side)) {
+            let (a, b) = self.split(self.nodes[t].right, below);
+            self.nodes[t].right = a;
+            self.update(t);
+            (t, b)
+        } else {
+            let (a, b) = self.split(self.nodes[t].left, below);
+            self.nodes[t].left = b;
+            self.update(t);
+            (a, t)
+        }
+    }
+
+    /// Joins two trees, all IDs in `a` being at most those in `b`.
+    fn merge(&mut self, a: usize, b: usize) -> usize {
+        if a == NIL {
+            return b;
+        }
+        if b == NIL {
+            return a;
+        }
+        if self.nodes[a].priority > self.nodes[b].priority {
+            let right = self.merge(self.nodes[a].right, b);
+            self.nodes[a].right = right;
+            self.update(a);
+            a
+        } else {
+            let left = self.merge(a, self.nodes[b].left);
+            self.nodes[b].left = left;
+            self.update(b);
+            b
+        }
+    }
+
+    fn insert(&mut self, id: u64, side: Side) {
+        let node = Node {
+            id,
+            side,
+            priority: self.rng.next_u64(),
+            left: NIL,
+            right: NIL,
+            summary: Summary::leaf(id, side),
+        };
+        let t = match self.free.pop() {
+            Some(t) => {
+                self.nodes[t] = node;
+                t
+            }
+            None => {
+                self.nodes.push(node);
+                self.nodes.len() - 1
+            }
+        };
+        let (a, b) = self.split(self.root, &|key| key < (id, side));
+        let a = self.merge(a, t);
+        self.root = self.merge(a, b);
+    }
+
+    /// Removes one `id` of `side`, returning whether there was one.
+    fn remove(&mut self, id: u64, side: Side) -> bool {
+        let (a, b) = self.split(self.root, &|key| key < (id, side));
+        let (equal, c) = self.split(b, &|key| key == (id, side));
+        let rest = if equal == NIL {
+            NIL
+        } else {
+            self.free.push(equal);
+            let Node { left, right, .. } = self.nodes[equal];
+            self.merge(left, right)
+        };
+        let a = self.merge(a, rest);
+        self.root = self.merge(a, c);
+        equal != NIL
+    }
+
+    fn len(&self, side: Side) -> usize {
+        self.summary(self.root)
+            .map_or(0, |s| s.count[side as usize])
+    }
+
+    /// Sum of the `k` smallest IDs of `side`.
+    fn prefix_sum(&self, side: Side, mut k: usize) -> u128 {
+        let mut sum = 0;
+        let mut t = self.root;
+        while k > 0 && t != NIL {
+            let node = &self.nodes[t];
+            let (count, below) = self
+                .summary(node.left)
+                .map_or((0, 0), |s| (s.count[side as usize], s.sum[side as usize]));
+            if k <= count {
+                t = node.left;
+                continue;
+            }
+            sum += below;
+            k -= count;
+            if node.side == side {
+                sum += node.id as u128;
+                k -= 1;
+            }
+            t = node.right;
+        }
+        sum
+    }
+
+    /// Sum over consecutive IDs below `t` of the gap between them times the
+    /// absolute balance in it, with balances starting from `offset`.
+    ///
+    /// Skips the subtrees in which the balance keeps its sign.
+    fn abs_area(&self, t: usize, offset: i64) -> i128 {
+        let Some(s) = self.summary(t) else {
+            return 0;
+        };
+        let shifted = || offset as i128 * (s.last - s.first) as i128 + s.area;
+        if offset + s.low >= 0 {
+            return shifted();
+        }
+        if offset + s.high <= 0 {
+            return -shifted();
+        }
+        let node = &self.nodes[t];
+        let mut area = self.abs_area(node.left, offset);
+        let mut balance = offset;
+        if let Some(l) = self.summary(node.left) {
+            balance += l.balance();
+            area += balance.abs() as i128 * (node.id - l.last) as i128;
+        }
+        balance += node.side.weight();
+        if let Some(r) = self.summary(node.right) {
+            area += balance.abs() as i128 * (r.first - node.id) as i128;
+            area += self.abs_area(node.right, balance);
+        }
+        area
+    }
+
+    /// Total distance between the sorted lists, pairing the smallest IDs of
+    /// the longer list only.
+    fn distance(&self) -> u64 {
+        let Some(s) = self.summary(self.root) else {
+            return 0;
+        };
+        // The distance is the area between the counts of IDs up to each value,
+        // where the balance is their difference.
+        let area = self.abs_area(self.root, 0);
+        // Unpaired IDs of the longer list add the area between them and the
+        // largest ID, which they should not.
+        let (longer, shorter) = if s.count[0] >= s.count[1] {
+            (Side::Left, Side::Right)
+        } else {
+            (Side::Right, Side::Left)
+        };
+        let paired = s.count[shorter as usize];
+        let unpaired = (s.count[longer as usize] - paired) as i128;
+        let top = (s.sum[longer as usize] - self.prefix_sum(longer, paired)) as i128;
+        (area - (unpaired * s.last as i128 - top)) as u64
+    }
+}
+
+/// Both location lists under edits, keeping parts 1 and 2 up to date.
+///
+/// The similarity score changes by `id` times the count of `id` in the other
+/// list, so it is updated in O(1). The distance is not as local: inserting an
+/// ID shifts the pairing of every larger ID by one rank. Instead of pairs, both
+/// lists are kept in one treap, updated in O(log n) expected time per edit. The
+/// distance is the area between the two lists' counts of IDs up to each value,
+/// which the treap sums in O(log n) for every stretch of IDs over which the
+/// same list stays ahead, without visiting single pairs.
+///
+/// With lists of different lengths the distance pairs the smallest IDs of the
+/// longer list only, like the puzzle's `zip`.
+#[derive(Clone, Debug, Default)]
+pub struct LocationLists {
+    lists: MergedLists,
+    left_counts: HashMap<u64, u64>,
+    right_counts: HashMap<u64, u64>,
+    similarity: u64,
+}
+
+impl LocationLists {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Lists holding `left` and `right`, in O(n log n).
+    pub fn from_lists(left: &[u64], right: &[u64]) -> Self {
+        let mut lists = Self::new();
+        for &id in left {
+            lists.insert_left(id);
+        }
+        for &id in right {
+            lists.insert_right(id);
+        }
+        lists
+    }
+
+    /// Total distance between the lists, part 1.
+    pub fn distance(&self) -> u64 {
+        self.lists.distance()
+    }
+
+    /// Similarity score of the lists, part 2.
+    pub fn similarity(&self) -> u64 {
+        self.similarity
+    }
+
+    pub fn len(&self, side: Side) -> usize {
+        self.lists.len(side)
+    }
+
+    /// How often `id` occurs in the list on `side`.
+    pub fn count_of(&self, side: Side, id: u64) -> u64 {
+        let counts = match side {
+            Side::Left => &self.left_counts,
+            Side::Right => &self.right_counts,
+        };
+        counts.get(&id).copied().unwrap_or(0)
+    }
+
+    pub fn insert_left(&mut self, id: u64) {
+        self.edit(Side::Left, id, true);
+    }
+
+    pub fn insert_right(&mut self, id: u64) {
+        self.edit(Side::Right, id, true);
+    }
+
+    /// Removes one `id` from the left list, returning whether there was one.
+    pub fn remove_left(&mut self, id: u64) -> bool {
+        self.edit(Side::Left, id, false)
+    }
+
+    /// Removes one `id` from the right list, returning whether there was one.
+    pub fn remove_right(&mut self, id: u64) -> bool {
+        self.edit(Side::Right, id, false)
+    }
+
+    /// Updates the counts and the similarity score for one ID added or removed.
+    fn count(&mut self, side: Side, id: u64, insert: bool) {
+        let (counts, other) = match side {
+            Side::Left => (&mut self.left_counts, &self.right_counts),
+            Side::Right => (&mut self.right_counts, &self.left_counts),
+        };
+        let score = id * other.get(&id).copied().unwrap_or(0);
+        let count = counts.entry(id).or_default();
+        if insert {
+            *count += 1;
+            self.similarity += score;
+        } else {
+            *count -= 1;
+            if *count == 0 {
+                counts.remove(&id);
+            }
+            self.similarity -= score;
+        }
+    }
+
+    fn edit(&mut self, side: Side, id: u64, insert: bool) -> bool {
+        if insert {
+            self.lists.insert(id, side);
+        } else if self.count_of(side, id) == 0 {
+            return false;
+        } else {
+            self.lists.remove(id, side);
+        }
+        self.count(side, id, insert);
+        true
+    }
+
+    /// Runs one line of the edit protocol, returning the reply or `None` to quit.
+    ///
+    /// Commands are `insert left|right ID`, `remove left|right ID`,
+    /// `distance`, `similarity`, `len` and `quit`.
+    pub fn execute(&mut self, line: &str) -> Result<Option<String>, String> {
+        let words: Vec<_> = line.split_whitespace().collect();
+        let reply = match words[..] {
+            [command @ ("insert" | "remove"), side, id] => {
+                let side = match side {
+                    "left" | "l" => Side::Left,
+                    "right" | "r" => Side::Right,
+                    _ => return Err(format!("{side} is not left or right")),
+                };
+                let id = id
+                    .parse()
+                    .map_err(|_| format!("{id} is not a location ID"))?;
+                if !self.edit(side, id, command == "insert") {
+                    return Err(format!("{id} is not in the {side} list"));
+                }
+                "ok".to_string()
+            }
+            ["distance"] => self.distance().to_string(),
+            ["similarity"] => self.similarity.to_string(),
+            ["len"] => format!("{} {}", self.len(Side::Left), self.len(Side::Right)),
+            ["quit"] => return Ok(None),
+            [] => return Err("Empty command".to_string()),
+            _ => return Err(format!("Unknown command {line:?}")),
+        };
+        Ok(Some(reply))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::days::day1::{part1, part2};
+
+    #[test]
+    fn test_merged_lists() {
+        let mut lists = MergedLists::default();
+        for (id, side) in [
+            (5, Side::Left),
+            (1, Side::Right),
+            (3, Side::Left),
+            (3, Side::Right),
+        ] {
+            lists.insert(id, side);
+        }
+        lists.insert(9, Side::Left);
+        assert_eq!((lists.len(Side::Left), lists.len(Side::Right)), (3, 2));
+        assert_eq!(lists.prefix_sum(Side::Left, 2), 8);
+        assert_eq!(lists.prefix_sum(Side::Right, 5), 4);
+        // Pairs (3, 1) and (5, 3), with 9 left over.
+        assert_eq!(lists.distance(), 4);
+        assert!(lists.remove(9, Side::Left));
+        assert!(!lists.remove(9, Side::Left));
+        assert!(!lists.remove(5, Side::Right));
+        assert_eq!(lists.distance(), 4);
+        // Freed nodes are reused.
+        lists.insert(0, Side::Right);
+        assert_eq!(lists.nodes.len(), 5);
+        assert_eq!(lists.distance(), 3 + 4);
+    }
+
+    #[test]
+    fn test_large_ids() {
+        let mut rng = Rng::new(3);
+        let mut left: Vec<_> = (0..500).map(|_| rng.next_u64() >> 12).collect();
+        let mut right: Vec<_> = (0..400).map(|_| rng.next_u64() >> 12).collect();
+        let lists = LocationLists::from_lists(&left, &right);
+        assert_eq!(lists.distance(), part1(&mut left, &mut right));
+    }
+
+    #[test]
+    fn test_random_edits() {
+        let mut rng = Rng::new(7);
+        let mut lists = LocationLists::new();
+        let (mut left, mut right) = (Vec::new(), Vec::new());
+        for _ in 0..2000 {
+            let id = rng.gen_range(1..30);
+            let (side, ids) = if rng.chance(0.5) {
+                (Side::Left, &mut left)
+            } else {
+                (Side::Right, &mut right)
+            };
+            if rng.chance(0.6) {
+                lists.edit(side, id, true);
+                ids.push(id);
+            } else {
+                let pos = ids.iter().position(|&x| x == id);
+                assert_eq!(lists.edit(side, id, false), pos.is_some());
+                if let Some(pos) = pos {
+                    ids.swap_remove(pos);
+                }
+            }
+            assert_eq!(
+                lists.distance(),
+                part1(&mut left.clone(), &mut right.clone())
+            );
+            assert_eq!(lists.similarity(), part2(&left, &right));
+        }
+        let rebuilt = LocationLists::from_lists(&left, &right);
+        assert_eq!(
+            (rebuilt.distance(), rebuilt.similarity()),
+            (lists.distance(), lists.similarity())
+        );
+    }
+
+    #[test]
+    fn test_execute() {
+        let mut lists = LocationLists::from_lists(&[3, 4, 2, 1, 3, 3], &[4, 3, 5, 3, 9, 3]);
+        let mut run = |line| lists.execute(line).map(|r| r.unwrap_or_default());
+        assert_eq!(run("distance"), Ok("11".to_string()));
+        assert_eq!(run("similarity"), Ok("31".to_string()));
+        assert_eq!(run("insert left 9"), Ok("ok".to_string()));
+        assert_eq!(run("insert r 10"), Ok("ok".to_string()));
+        assert_eq!(run("similarity"), Ok("40".to_string()));
+        assert_eq!(run("distance"), Ok("12".to_string()));
+        assert_eq!(run("remove right 3"), Ok("ok".to_string()));
+        assert_eq!(run("len"), Ok("7 6".to_string()));
+        assert_eq!(
+            run("remove left 7"),
+            Err("7 is not in the left list".to_string())
+        );
+        assert_eq!(run("len"), Ok("7 6".to_string()));
+        assert!(run("insert up 1").is_err());
+        assert!(run("insert left x").is_err());
+        assert!(run("fly").is_err());
+        assert_eq!(lists.execute("quit"), Ok(None));
+    }
+}
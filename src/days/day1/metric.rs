@@ -0,0 +1,399 @@
+use crate::parse::{self, ParseError};
+use std::collections::HashMap;
+use std::fmt;
+use std::iter::zip;
+
+/// Slack for rounding errors when checking whether a cost table is Monge.
+const EPSILON: f64 = 1e-9;
+
+/// Most pairs [`min_cost_matching`] runs the Hungarian algorithm on.
+///
+/// It keeps a dense table of `n * n` costs, 32 MB at the limit, and takes
+/// O(n³) time, a few seconds at the limit.
+pub const MAX_HUNGARIAN: usize = 2000;
+
+/// How much it costs to pair a left location ID with a right one.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Metric {
+    /// `|l - r|`, the puzzle's distance.
+    Absolute,
+    /// `(l - r)²`.
+    Squared,
+    /// `|ln(l / r)|`, defined for positive IDs only.
+    LogRatio,
+    /// Costs given for some pairs, the absolute difference for all others.
+    Table(CostTable),
+}
+
+impl Metric {
+    /// The cost of pairing `l` with `r`, as a float for comparing costs.
+    pub fn cost(&self, l: u64, r: u64) -> f64 {
+        match self {
+            Metric::Absolute => l.abs_diff(r) as f64,
+            Metric::Squared => (l.abs_diff(r) as f64).powi(2),
+            Metric::LogRatio => ((l as f64).ln() - (r as f64).ln()).abs(),
+            Metric::Table(table) => table.cost(l, r),
+        }
+    }
+
+    /// The exact cost of pairing `l` with `r`, an integer for the integer valued metrics.
+    pub fn pair_cost(&self, l: u64, r: u64) -> Cost {
+        let diff = l.abs_diff(r) as u128;
+        match self {
+            Metric::Absolute => Cost::Integer(diff),
+            // Fits, as the square of a 64 bit number has at most 128 bits.
+            Metric::Squared => Cost::Integer(diff * diff),
+            Metric::LogRatio | Metric::Table(_) => Cost::Real(self.cost(l, r)),
+        }
+    }
+
+    /// Whether pairing both lists in sorted order is a minimum cost matching,
+    /// which it is whenever the costs over the sorted IDs form a Monge array.
+    ///
+    /// Costs `f(g(l) - g(r))` with a convex `f` and an increasing `g` always do,
+    /// so that only tables need to be checked on the IDs at hand.
+    fn sorted_is_optimal(&self, left: &[u64], right: &[u64]) -> bool {
+        match self {
+            // f(x) = |x| and g(x) = x.
+            Metric::Absolute => true,
+            // f(x) = x² and g(x) = x.
+            Metric::Squared => true,
+            // f(x) = |x| and g(x) = ln x.
+            Metric::LogRatio => true,
+            Metric::Table(_) => is_monge(left, right, |l, r| self.cost(l, r)),
+        }
+    }
+}
+
+/// The total cost of pairs, exact for the integer valued metrics.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Cost {
+    Integer(u128),
+    Real(f64),
+}
+
+impl Cost {
+    pub fn to_f64(self) -> f64 {
+        match self {
+            Cost::Integer(n) => n as f64,
+            Cost::Real(x) => x,
+        }
+    }
+
+    /// `self + other`, `None` if an integer sum overflows.
+    fn checked_add(self, other: Cost) -> Option<Cost> {
+        match (self, other) {
+            (Cost::Integer(a), Cost::Integer(b)) => a.checked_add(b).map(Cost::Integer),
+            (a, b) => Some(Cost::Real(a.to_f64() + b.to_f64())),
+        }
+    }
+}
+
+impl fmt::Display for Cost {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Cost::Integer(n) => write!(f, "{n}"),
+            Cost::Real(x) => write!(f, "{x}"),
+        }
+    }
+}
+
+/// Costs for specific pairs, read from lines of `LEFT RIGHT COST`.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct CostTable(HashMap<(u64, u64), f64>);
+
+impl CostTable {
+    pub fn parse(input: &str) -> Result<Self, ParseError> {
+        let mut costs = HashMap::new();
+        for line in parse::lines(input) {
+            let mut it = line.text.split_whitespace();
+            let l = line.parse_next(&mut it, "a location ID")?;
+            let r = line.parse_next(&mut it, "a location ID")?;
+            let token = line.next(&mut it, "a cost")?;
+            let cost: f64 = line.parse(token, "a finite cost")?;
+            if !cost.is_finite() {
+                return Err(line.error(token, "a finite cost"));
+            }
+            line.end(&mut it)?;
+            costs.insert((l, r), cost);
+        }
+        Ok(Self(costs))
+    }
+
+    pub fn cost(&self, l: u64, r: u64) -> f64 {
+        self.0.get(&(l, r)).copied().unwrap_or(l.abs_diff(r) as f64)
+    }
+}
+
+/// Whether `cost` over the distinct sorted IDs satisfies the Monge property
+/// `c(a, b) + c(a', b') <= c(a, b') + c(a', b)` for `a < a'` and `b < b'`.
+///
+/// Checking neighbouring rows and columns is enough.
+fn is_monge(left: &[u64], right: &[u64], cost: impl Fn(u64, u64) -> f64) -> bool {
+    let distinct = |ids: &[u64]| {
+        let mut ids = ids.to_vec();
+        ids.sort_unstable();
+        ids.dedup();
+        ids
+    };
+    let (rows, cols) = (distinct(left), distinct(right));
+    rows.windows(2).all(|a| {
+        cols.windows(2).all(|b| {
+            cost(a[0], b[0]) + cost(a[1], b[1]) <= cost(a[0], b[1]) + cost(a[1], b[0]) + EPSILON
+        })
+    })
+}
+
+/// How a [`Matching`] was found.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Method {
+    /// Both lists sorted and paired in order, in O(n log n).
+    Sorted,
+    /// The Hungarian algorithm, in O(n³).
+    Hungarian,
+}
+
+impl fmt::Display for Method {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Method::Sorted => write!(f, "sorted pairing"),
+            Method::Hungarian => write!(f, "Hungarian algorithm"),
+        }
+    }
+}
+
+/// A one-to-one assignment of left IDs to right IDs.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Matching {
+    /// Left and right ID of each pair, ordered by left ID.
+    pub pairs: Vec<(u64, u64)>,
+    pub cost: Cost,
+    pub method: Method,
+}
+
+/// The minimum cost one-to-one assignment between `left` and `right` under `metric`.
+///
+/// Fails for metrics that need the Hungarian algorithm on more than
+/// [`MAX_HUNGARIAN`] pairs.
+pub fn min_cost_matching(left: &[u64], right: &[u64], metric: &Metric) -> Result<Matching, String> {
+    if left.len() != right.len() {
+        return Err(format!(
+            "Cannot match {} left IDs with {} right IDs",
+            left.len(),
+            right.len()
+        ));
+    }
+    if *metric == Metric::LogRatio && left.iter().chain(right).any(|&id| id == 0) {
+        return Err("The log-ratio metric needs positive IDs".to_string());
+    }
+    let (mut pairs, method) = if metric.sorted_is_optimal(left, right) {
+        let mut left = left.to_vec();
+        let mut right = right.to_vec();
+        left.sort_unstable();
+        right.sort_unstable();
+        (zip(left, right).collect::<Vec<_>>(), Method::Sorted)
+    } else if left.len() > MAX_HUNGARIAN {
+        return Err(format!(
+            "Cannot match {} pairs under costs that are not Monge, at most {MAX_HUNGARIAN} are supported",
+            left.len()
+        ));
+    } else {
+        let costs: Vec<_> = left
+            .iter()
+            .flat_map(|&l| right.iter().map(move |&r| metric.cost(l, r)))
+            .collect();
+        let pairs = hungarian(left.len(), |i, j| costs[i * right.len() + j])
+            .into_iter()
+            .enumerate()
+            .map(|(i, j)| (left[i], right[j]))
+            .collect();
+        (pairs, Method::Hungarian)
+    };
+    pairs.sort_unstable();
+    let zero = match metric {
+        Metric::Absolute | Metric::Squared => Cost::Integer(0),
+        Metric::LogRatio | Metric::Table(_) => Cost::Real(0.0),
+    };
+    let cost = pairs
+        .iter()
+        .try_fold(zero, |sum, &(l, r)| sum.checked_add(metric.pair_cost(l, r)))
+        .ok_or("The total cost does not fit into 128 bits")?;
+    Ok(Matching {
+        pairs,
+        cost,
+        method,
+    })
+}
+
+/// Minimum cost perfect matching in the complete bipartite graph with `n`
+/// rows and columns, returning the column assigned to each row.
+///
+/// Adds one row at a time, keeping potentials on rows and columns so that
+/// reduced costs stay non-negative while growing shortest augmenting paths.
+fn hungarian(n: usize, cost: impl Fn(usize, usize) -> f64) -> Vec<usize> {
+    // Rows and columns count from 1, with column 0 as the root of each search.
+    let mut u = vec![0.0; n + 1];
+    let mut v = vec![0.0; n + 1];
+    // The row matched to each column, 0 if none.
+    let mut row_of = vec![0; n + 1];
+    let mut way = vec![0; n + 1];
+    for row in 1..=n {
+        row_of[0] = row;
+        let mut col = 0;
+        let mut min_slack = vec![f64::INFINITY; n + 1];
+        let mut used = vec![false; n + 1];
+        while row_of[col] != 0 {
+            used[col] = true;
+            let i = row_of[col];
+            let mut delta = f64::INFINITY;
+            let mut next = 0;
+            for j in 1..=n {
+                if used[j] {
+                    continue;
+                }
+                let slack = cost(i - 1, j - 1) - u[i] - v[j];
+                if slack < min_slack[j] {
+                    min_slack[j] = slack;
+                    way[j] = col;
+                }
+                if min_slack[j] < delta {
+                    delta = min_slack[j];
+                    next = j;
+                }
+            }
+            for j in 0..=n {
+                if used[j] {
+                    u[row_of[j]] += delta;
+                    v[j] -= delta;
+                } else {
+                    min_slack[j] -= delta;
+                }
+            }
+            col = next;
+        }
+        // Flip the augmenting path back to the root.
+        while col != 0 {
+            let prev = way[col];
+            row_of[col] = row_of[prev];
+            col = prev;
+        }
+    }
+    let mut assignment = vec![0; n];
+    for col in 1..=n {
+        assignment[row_of[col] - 1] = col - 1;
+    }
+    assignment
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::rng::Rng;
+    use itertools::Itertools;
+
+    const LEFT: [u64; 6] = [3, 4, 2, 1, 3, 3];
+    const RIGHT: [u64; 6] = [4, 3, 5, 3, 9, 3];
+
+    fn brute_force(left: &[u64], right: &[u64], metric: &Metric) -> f64 {
+        (0..right.len())
+            .permutations(right.len())
+            .map(|p| {
+                (0..left.len())
+                    .map(|i| metric.cost(left[i], right[p[i]]))
+                    .sum()
+            })
+            .fold(f64::INFINITY, f64::min)
+    }
+
+    #[test]
+    fn test_absolute() {
+        let m = min_cost_matching(&LEFT, &RIGHT, &Metric::Absolute).unwrap();
+        assert_eq!((m.cost, m.method), (Cost::Integer(11), Method::Sorted));
+        assert_eq!(m.pairs, [(1, 3), (2, 3), (3, 3), (3, 4), (3, 5), (4, 9)]);
+    }
+
+    #[test]
+    fn test_hungarian() {
+        let mut rng = Rng::new(1);
+        for n in 1..=6 {
+            let left: Vec<_> = (0..n).map(|_| rng.gen_range(1..20)).collect();
+            let right: Vec<_> = (0..n).map(|_| rng.gen_range(1..20)).collect();
+            let mut costs = HashMap::new();
+            for (&l, &r) in left.iter().cartesian_product(&right) {
+                costs.insert((l, r), rng.gen_range(0..100) as f64);
+            }
+            let metric = Metric::Table(CostTable(costs));
+            let m = min_cost_matching(&left, &right, &metric).unwrap();
+            assert_eq!(m.cost, Cost::Real(brute_force(&left, &right, &metric)));
+            assert!(
+                zip(m.pairs.iter().map(|p| p.0).sorted(), left.iter().sorted())
+                    .all(|(a, b)| a == *b)
+            );
+            assert!(
+                zip(m.pairs.iter().map(|p| p.1).sorted(), right.iter().sorted())
+                    .all(|(a, b)| a == *b)
+            );
+        }
+    }
+
+    #[test]
+    fn test_sorted_is_optimal() {
+        let mut rng = Rng::new(2);
+        for metric in [Metric::Absolute, Metric::Squared, Metric::LogRatio] {
+            for _ in 0..20 {
+                let left: Vec<_> = (0..6).map(|_| rng.gen_range(1..1000)).collect();
+                let right: Vec<_> = (0..6).map(|_| rng.gen_range(1..1000)).collect();
+                let m = min_cost_matching(&left, &right, &metric).unwrap();
+                assert_eq!(m.method, Method::Sorted);
+                assert!(is_monge(&left, &right, |l, r| metric.cost(l, r)));
+                assert!((m.cost.to_f64() - brute_force(&left, &right, &metric)).abs() < 1e-9);
+            }
+        }
+    }
+
+    #[test]
+    fn test_table() {
+        // Pairing 3 with 3 is expensive, so the sorted pairing is no longer optimal.
+        let table = CostTable::parse("3 3 10\n1 4 0.5\n").unwrap();
+        assert_eq!(table.cost(1, 4), 0.5);
+        assert_eq!(table.cost(4, 1), 3.0);
+        let metric = Metric::Table(table);
+        let m = min_cost_matching(&LEFT, &RIGHT, &metric).unwrap();
+        assert_eq!(m.method, Method::Hungarian);
+        assert_eq!(m.cost, Cost::Real(brute_force(&LEFT, &RIGHT, &metric)));
+
+        // Costs agreeing with the absolute difference keep the sorted pairing.
+        let metric = Metric::Table(CostTable::parse("3 3 0\n1 4 3\n").unwrap());
+        let m = min_cost_matching(&LEFT, &RIGHT, &metric).unwrap();
+        assert_eq!((m.cost, m.method), (Cost::Real(11.0), Method::Sorted));
+
+        let err = CostTable::parse("1 2 3\n1 2 inf\n").unwrap_err();
+        assert_eq!((err.line, err.column), (2, 5));
+        let err = CostTable::parse("1 2\n").unwrap_err();
+        assert_eq!(err.expected, "a cost");
+    }
+
+    #[test]
+    fn test_errors() {
+        assert!(min_cost_matching(&[1, 2], &[1], &Metric::Absolute).is_err());
+        assert!(min_cost_matching(&[0], &[1], &Metric::LogRatio).is_err());
+        assert!(min_cost_matching(&[0], &[1], &Metric::Squared).is_ok());
+
+        // Sorted pairings have no size limit, the Hungarian algorithm does.
+        let ids: Vec<_> = (0..MAX_HUNGARIAN as u64 + 1).collect();
+        let table = Metric::Table(CostTable::parse("0 0 100\n").unwrap());
+        assert!(min_cost_matching(&ids, &ids, &Metric::Absolute).is_ok());
+        assert!(min_cost_matching(&ids, &ids, &table).is_err());
+    }
+
+    #[test]
+    fn test_exact_cost() {
+        // Too large for a sum of floats to keep the 1.
+        let big = 1 << 50;
+        let m = min_cost_matching(&[0, 2 * big], &[big, 1], &Metric::Squared).unwrap();
+        assert_eq!(m.cost, Cost::Integer((1 << 100) + 1));
+        assert_eq!(m.cost.to_string(), "1267650600228229401496703205377");
+        let max = u64::MAX;
+        assert!(min_cost_matching(&[0, 0], &[max, max], &Metric::Squared).is_err());
+    }
+}
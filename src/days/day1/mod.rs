@@ -0,0 +1,121 @@
+pub mod external;
+pub mod incremental;
+pub mod metric;
+pub mod report;
+
+use crate::parse::{self, Line, ParseError};
+use crate::rng::Rng;
+use crate::solution::{Answer, Solution};
+use std::cmp::{max, min};
+use std::collections::HashMap;
+use std::iter::zip;
+
+pub struct Day1;
+
+impl Solution for Day1 {
+    const DAY: u32 = 1;
+    type Input = (Vec<u64>, Vec<u64>);
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1((left, right): &Self::Input) -> Answer {
+        part1(&mut left.clone(), &mut right.clone()).into()
+    }
+
+    fn part2((left, right): &Self::Input) -> Answer {
+        part2(left, right).into()
+    }
+
+    fn generate(rng: &mut Rng, size: usize) -> Option<String> {
+        Some(generate(rng, size))
+    }
+}
+
+fn parse_input(input: &str) -> Result<(Vec<u64>, Vec<u64>), ParseError> {
+    let mut left = vec![];
+    let mut right = vec![];
+    for line in parse::lines(input) {
+        let (l, r) = parse_line(line)?;
+        left.push(l);
+        right.push(r);
+    }
+    Ok((left, right))
+}
+
+/// The left and right location ID on one line.
+fn parse_line(line: Line) -> Result<(u64, u64), ParseError> {
+    let mut it = line.text.split_whitespace();
+    let left = line.parse_next(&mut it, "a location ID")?;
+    let right = line.parse_next(&mut it, "a location ID")?;
+    line.end(&mut it)?;
+    Ok((left, right))
+}
+
+fn part1(left: &mut Vec<u64>, right: &mut Vec<u64>) -> u64 {
+    left.sort();
+    right.sort();
+    zip(left, right)
+        .map(|x| max(*x.0, *x.1) - min(*x.0, *x.1))
+        .sum()
+}
+
+fn part2(left: &[u64], right: &[u64]) -> u64 {
+    let mut ctr = HashMap::new();
+    for x in right.iter() {
+        ctr.entry(x).and_modify(|c| *c += 1).or_insert(1);
+    }
+    let mut res = 0u64;
+    for x in left.iter() {
+        res += x * ctr.get(x).unwrap_or(&0);
+    }
+    res
+}
+
+/// `size` lines of location ID pairs, with some IDs shared between the lists.
+fn generate(rng: &mut Rng, size: usize) -> String {
+    let mut left = Vec::with_capacity(size);
+    for _ in 0..size {
+        left.push(rng.gen_range(10000..100000));
+    }
+    let mut out = String::new();
+    for &l in &left {
+        let r = if rng.chance(0.5) {
+            *rng.choose(&left)
+        } else {
+            rng.gen_range(10000..100000)
+        };
+        out += &format!("{l}   {r}\n");
+    }
+    out
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::examples;
+
+    #[test]
+    fn test_parse_error() {
+        let err = parse_input("3   4\n4\n").unwrap_err();
+        assert_eq!((err.line, err.column), (2, 2));
+        let err = parse_input("3   4\n4   x3\n").unwrap_err();
+        assert_eq!((err.line, err.column, err.text.as_str()), (2, 5, "x3"));
+        let err = parse_input("3   4   5\n").unwrap_err();
+        assert_eq!(
+            (err.line, err.column, err.expected.as_str()),
+            (1, 9, "end of line")
+        );
+    }
+
+    #[test]
+    fn test_part1() {
+        examples::check::<Day1>(1);
+    }
+
+    #[test]
+    fn test_part2() {
+        examples::check::<Day1>(2);
+    }
+}
@@ -0,0 +1,260 @@
+use crate::table::Table;
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::fmt;
+
+/// How often an ID occurs.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+pub struct Count {
+    pub id: u64,
+    pub count: u64,
+}
+
+/// An ID occurring a different number of times in the two lists.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+pub struct Delta {
+    pub id: u64,
+    pub left: u64,
+    pub right: u64,
+    /// `right - left`.
+    pub delta: i64,
+}
+
+/// An ID adding to the similarity score.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize)]
+pub struct Contributor {
+    pub id: u64,
+    pub left: u64,
+    pub right: u64,
+    /// `id * left * right`.
+    pub score: u64,
+    /// Fraction of the whole similarity score.
+    pub share: f64,
+}
+
+/// How the two location lists differ, taken as multisets.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct Report {
+    pub left_len: usize,
+    pub right_len: usize,
+    /// Part 2's similarity score.
+    pub similarity: u64,
+    /// Shared distinct IDs over all distinct IDs, 1 for two empty lists.
+    pub jaccard: f64,
+    /// Sum of the smaller over sum of the larger count of every ID, 1 for two empty lists.
+    pub weighted_jaccard: f64,
+    /// Each ID with the smaller of its two counts, by ID.
+    pub intersection: Vec<Count>,
+    /// IDs left over in the left list after removing the intersection, by ID.
+    pub left_only: Vec<Count>,
+    /// IDs left over in the right list after removing the intersection, by ID.
+    pub right_only: Vec<Count>,
+    /// Every ID whose counts differ, by ID.
+    pub deltas: Vec<Delta>,
+    /// The IDs adding the most to the similarity score, largest first.
+    pub top_contributors: Vec<Contributor>,
+}
+
+/// Compares `left` with `right`, listing up to `top` contributors to the similarity score.
+pub fn report(left: &[u64], right: &[u64], top: usize) -> Report {
+    // Counts in the left and the right list by ID.
+    let mut counts: BTreeMap<u64, (u64, u64)> = BTreeMap::new();
+    for &id in left {
+        counts.entry(id).or_default().0 += 1;
+    }
+    for &id in right {
+        counts.entry(id).or_default().1 += 1;
+    }
+
+    let mut report = Report {
+        left_len: left.len(),
+        right_len: right.len(),
+        similarity: 0,
+        jaccard: 1.0,
+        weighted_jaccard: 1.0,
+        intersection: Vec::new(),
+        left_only: Vec::new(),
+        right_only: Vec::new(),
+        deltas: Vec::new(),
+        top_contributors: Vec::new(),
+    };
+    let (mut shared, mut min_sum, mut max_sum) = (0, 0, 0);
+    let count = |id, count| Count { id, count };
+    for (&id, &(l, r)) in &counts {
+        let common = l.min(r);
+        min_sum += common;
+        max_sum += l.max(r);
+        report.similarity += id * l * r;
+        if common > 0 {
+            shared += 1;
+            report.intersection.push(count(id, common));
+        }
+        if l > common {
+            report.left_only.push(count(id, l - common));
+        }
+        if r > common {
+            report.right_only.push(count(id, r - common));
+        }
+        if l != r {
+            report.deltas.push(Delta {
+                id,
+                left: l,
+                right: r,
+                delta: r as i64 - l as i64,
+            });
+        }
+    }
+    if !counts.is_empty() {
+        report.jaccard = shared as f64 / counts.len() as f64;
+        report.weighted_jaccard = min_sum as f64 / max_sum as f64;
+    }
+
+    let mut contributors: Vec<_> = counts
+        .iter()
+        .map(|(&id, &(l, r))| (id, l, r, id * l * r))
+        .filter(|c| c.3 > 0)
+        .collect();
+    contributors.sort_by_key(|&(id, _, _, score)| (std::cmp::Reverse(score), id));
+    report.top_contributors = contributors
+        .into_iter()
+        .take(top)
+        .map(|(id, left, right, score)| Contributor {
+            id,
+            left,
+            right,
+            score,
+            share: score as f64 / report.similarity as f64,
+        })
+        .collect();
+    report
+}
+
+impl Report {
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).expect("Reports are serializable")
+    }
+}
+
+fn counts_table(f: &mut fmt::Formatter<'_>, title: &str, counts: &[Count]) -> fmt::Result {
+    writeln!(
+        f,
+        "\n{title} ({} IDs)",
+        counts.iter().map(|c| c.count).sum::<u64>()
+    )?;
+    if counts.is_empty() {
+        return writeln!(f, "(none)");
+    }
+    let mut table = Table::new(["id", "count"]);
+    for c in counts {
+        table.row([c.id, c.count]);
+    }
+    write!(f, "{table}")
+}
+
+impl fmt::Display for Report {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Left list: {} IDs", self.left_len)?;
+        writeln!(f, "Right list: {} IDs", self.right_len)?;
+        writeln!(f, "Similarity score: {}", self.similarity)?;
+        writeln!(f, "Jaccard similarity: {:.4}", self.jaccard)?;
+        writeln!(
+            f,
+            "Weighted Jaccard similarity: {:.4}",
+            self.weighted_jaccard
+        )?;
+
+        writeln!(f, "\nTop contributors to the similarity score")?;
+        if self.top_contributors.is_empty() {
+            writeln!(f, "(none)")?;
+        } else {
+            let mut table = Table::new(["id", "left", "right", "score", "share"]);
+            for c in &self.top_contributors {
+                table.row([
+                    c.id.to_string(),
+                    c.left.to_string(),
+                    c.right.to_string(),
+                    c.score.to_string(),
+                    format!("{:.2}%", 100.0 * c.share),
+                ]);
+            }
+            write!(f, "{table}")?;
+        }
+
+        counts_table(f, "Intersection", &self.intersection)?;
+        counts_table(f, "Only in the left list", &self.left_only)?;
+        counts_table(f, "Only in the right list", &self.right_only)?;
+
+        writeln!(f, "\nCount deltas ({} IDs)", self.deltas.len())?;
+        if self.deltas.is_empty() {
+            return writeln!(f, "(none)");
+        }
+        let mut table = Table::new(["id", "left", "right", "delta"]);
+        for d in &self.deltas {
+            table.row([
+                d.id.to_string(),
+                d.left.to_string(),
+                d.right.to_string(),
+                format!("{:+}", d.delta),
+            ]);
+        }
+        write!(f, "{table}")
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const LEFT: [u64; 6] = [3, 4, 2, 1, 3, 3];
+    const RIGHT: [u64; 6] = [4, 3, 5, 3, 9, 3];
+
+    #[test]
+    fn test_report() {
+        let r = report(&LEFT, &RIGHT, 2);
+        assert_eq!(r.similarity, 31);
+        // Distinct IDs 1, 2, 3, 4, 5 and 9, of which 3 and 4 are shared.
+        assert_eq!(r.jaccard, 2.0 / 6.0);
+        // Smaller counts add up to 4, larger ones to 8.
+        assert_eq!(r.weighted_jaccard, 0.5);
+        let count = |id, count| Count { id, count };
+        assert_eq!(r.intersection, [count(3, 3), count(4, 1)]);
+        assert_eq!(r.left_only, [count(1, 1), count(2, 1)]);
+        assert_eq!(r.right_only, [count(5, 1), count(9, 1)]);
+        assert_eq!(
+            r.deltas.iter().map(|d| (d.id, d.delta)).collect::<Vec<_>>(),
+            [(1, -1), (2, -1), (5, 1), (9, 1)]
+        );
+        assert_eq!(
+            r.top_contributors
+                .iter()
+                .map(|c| (c.id, c.score))
+                .collect::<Vec<_>>(),
+            [(3, 27), (4, 4)]
+        );
+        assert_eq!(r.top_contributors[1].share, 4.0 / 31.0);
+    }
+
+    #[test]
+    fn test_empty() {
+        let r = report(&[], &[], 10);
+        assert_eq!((r.jaccard, r.weighted_jaccard), (1.0, 1.0));
+        assert!(r.top_contributors.is_empty());
+        let r = report(&[1, 2], &[3], 10);
+        assert_eq!((r.jaccard, r.weighted_jaccard, r.similarity), (0.0, 0.0, 0));
+        assert!(r.to_string().contains("Intersection (0 IDs)\n(none)"));
+    }
+
+    #[test]
+    fn test_output() {
+        let r = report(&LEFT, &RIGHT, 1);
+        let text = r.to_string();
+        assert!(text.starts_with("Left list: 6 IDs\nRight list: 6 IDs\nSimilarity score: 31\n"));
+        assert!(text.contains("id  left  right  score  share\n3   3     3      27     87.10%\n"));
+        assert!(text.contains("1   1     0      -1\n"));
+        let json: serde_json::Value = serde_json::from_str(&r.to_json()).unwrap();
+        assert_eq!(json["similarity"], 31);
+        assert_eq!(json["left_only"][1]["id"], 2);
+        assert_eq!(json["deltas"][3]["delta"], 1);
+        assert_eq!(json["top_contributors"].as_array().unwrap().len(), 1);
+    }
+}
@@ -0,0 +1,186 @@
+use crate::geometry::{Direction4, Point};
+use crate::grid::Grid;
+use crate::parse::ParseError;
+use crate::render::{self, Color, Frames};
+use crate::rng::Rng;
+use crate::search;
+use crate::solution::{Answer, Solution};
+use std::collections::HashSet;
+
+pub struct Day10;
+
+impl Solution for Day10 {
+    const DAY: u32 = 10;
+    type Input = Grid<u8>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1(input: &Self::Input) -> Answer {
+        part1_2(input).0.into()
+    }
+
+    fn part2(input: &Self::Input) -> Answer {
+        part1_2(input).1.into()
+    }
+
+    fn part1_reference(input: &Self::Input) -> Option<Answer> {
+        Some(part1_2_reference(input).0.into())
+    }
+
+    fn part2_reference(input: &Self::Input) -> Option<Answer> {
+        Some(part1_2_reference(input).1.into())
+    }
+
+    fn generate(rng: &mut Rng, size: usize) -> Option<String> {
+        Some(generate(rng, size))
+    }
+
+    fn frames(input: &Self::Input) -> Option<Frames<'_>> {
+        Some(Box::new(std::iter::once(trail_map(input))))
+    }
+}
+
+fn parse_input(input: &str) -> Result<Grid<u8>, ParseError> {
+    Grid::parse(input, |c| c.to_digit(10).map(|h| h as u8).ok_or("a height"))
+}
+
+/// Positions next to `pos` that are exactly one higher.
+fn find_neighbors(map: &Grid<u8>, pos: Point) -> impl Iterator<Item = Point> + '_ {
+    let height = map[pos] + 1;
+    Direction4::ALL
+        .into_iter()
+        .filter_map(move |d| map.step(pos, d.vec()))
+        .filter(move |&n| map[n] == height)
+}
+
+/// The number of summits reachable from each trailhead and the number of
+/// distinct trails, both summed over all trailheads.
+fn part1_2(input: &Grid<u8>) -> (usize, usize) {
+    let mut res1 = 0;
+    let mut res2 = 0;
+
+    for (pos, &height) in input.iter() {
+        if height == 0 {
+            let uphill = |p: &Point| find_neighbors(input, *p);
+            let summit = |p: &Point| input[*p] == 9;
+            res1 += search::bfs_reach(pos, uphill)
+                .iter()
+                .filter(|(p, _)| summit(p))
+                .count();
+            res2 += search::count_paths(pos, uphill, summit);
+        }
+    }
+
+    (res1, res2)
+}
+
+/// [`part1_2`] by walking every trail on its own.
+fn part1_2_reference(input: &Grid<u8>) -> (usize, usize) {
+    let mut res1 = 0;
+    let mut res2 = 0;
+
+    for (pos, &height) in input.iter() {
+        if height == 0 {
+            let mut to_check = Vec::new();
+            to_check.extend(find_neighbors(input, pos));
+            let mut summits = HashSet::new();
+            while let Some(p) = to_check.pop() {
+                if input[p] == 9 {
+                    summits.insert(p);
+                    res2 += 1
+                } else {
+                    to_check.extend(find_neighbors(input, p));
+                }
+            }
+            res1 += summits.len();
+        }
+    }
+
+    (res1, res2)
+}
+
+/// Heights in shades of grey, with everything reachable from a trailhead in
+/// yellow, trailheads leading to a summit in green and those summits in red.
+fn trail_map(input: &Grid<u8>) -> Grid<Color> {
+    let shade = |h: u8| h as f64 / 9.0;
+    let mut colors = input.map(|&h| render::blend(render::DARK, render::GREY, shade(h)));
+    let mut ends = Vec::new();
+    for (pos, &height) in input.iter() {
+        if height == 0 {
+            let reached = search::bfs_reach(pos, |p: &Point| find_neighbors(input, *p));
+            let summits: Vec<_> = reached.iter().filter(|(p, _)| input[**p] == 9).collect();
+            if summits.is_empty() {
+                continue;
+            }
+            for (p, _) in reached.iter() {
+                colors[*p] =
+                    render::blend(render::DARK, render::YELLOW, 0.3 + 0.7 * shade(input[*p]));
+            }
+            ends.push(pos);
+            ends.extend(summits.into_iter().map(|(p, _)| *p));
+        }
+    }
+    for p in ends {
+        colors[p] = if input[p] == 0 {
+            render::GREEN
+        } else {
+            render::RED
+        };
+    }
+    colors
+}
+
+/// A `size` by `size` topographic map of random heights with some hiking trails.
+fn generate(rng: &mut Rng, size: usize) -> String {
+    let size = size.max(1);
+    let mut map = Grid::from_vec(size, size, vec![0u8; size * size]);
+    for pos in map.positions().collect::<Vec<_>>() {
+        map[pos] = rng.below(10) as u8;
+    }
+    for _ in 0..size {
+        let mut trail = vec![Point::new(rng.index(size), rng.index(size))];
+        while trail.len() < 10 {
+            let next: Vec<_> = map
+                .neighbors4(*trail.last().unwrap())
+                .filter(|p| !trail.contains(p))
+                .collect();
+            if next.is_empty() {
+                break;
+            }
+            trail.push(*rng.choose(&next));
+        }
+        if trail.len() == 10 {
+            for (height, pos) in trail.into_iter().enumerate() {
+                map[pos] = height as u8;
+            }
+        }
+    }
+    map.map(|h| (b'0' + h) as char).to_string()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::examples;
+
+    #[test]
+    fn test_part1() {
+        examples::check::<Day10>(1);
+    }
+
+    #[test]
+    fn test_part2() {
+        examples::check::<Day10>(2);
+    }
+
+    #[test]
+    fn test_reference() {
+        let mut rng = Rng::new(10);
+        for size in [1, 5, 20, 40] {
+            let map = parse_input(&generate(&mut rng, size)).unwrap();
+            assert_eq!(part1_2(&map), part1_2_reference(&map));
+        }
+    }
+}
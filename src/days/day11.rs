@@ -0,0 +1,177 @@
+use crate::parse::ParseError;
+use crate::rng::Rng;
+use crate::solution::{Answer, Solution};
+use crate::stepper::Stepper;
+use std::collections::HashMap;
+
+pub struct Day11;
+
+impl Solution for Day11 {
+    const DAY: u32 = 11;
+    type Input = Vec<u64>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1(input: &Self::Input) -> Answer {
+        part1(input).into()
+    }
+
+    fn part2(input: &Self::Input) -> Answer {
+        part2(input.clone()).into()
+    }
+
+    fn part1_reference(input: &Self::Input) -> Option<Answer> {
+        Some(part1_reference(input.clone()).into())
+    }
+
+    fn generate(rng: &mut Rng, size: usize) -> Option<String> {
+        Some(generate(rng, size))
+    }
+
+    fn stepper(input: &Self::Input) -> Option<Box<dyn Stepper>> {
+        Some(Box::new(Blinking(input.clone())))
+    }
+}
+
+fn parse_input(input: &str) -> Result<Vec<u64>, ParseError> {
+    input
+        .split_whitespace()
+        .map(|x| x.parse().map_err(|_| ParseError::at(input, x, "a stone")))
+        .collect()
+}
+
+/// The stones a single stone turns into after one blink.
+fn blink(stone: u64) -> (u64, Option<u64>) {
+    if stone == 0 {
+        return (1, None);
+    }
+    let digits = stone.ilog10() + 1;
+    if digits.is_multiple_of(2) {
+        let half = 10u64.pow(digits / 2);
+        (stone / half, Some(stone % half))
+    } else {
+        (stone * 2024, None)
+    }
+}
+
+/// Number of stones after `blinks` blinks, tracking how often each value occurs
+/// instead of the stones themselves.
+fn count_stones(input: &[u64], blinks: usize) -> usize {
+    let mut counts: HashMap<u64, usize> = HashMap::new();
+    for &stone in input {
+        *counts.entry(stone).or_default() += 1;
+    }
+    for _ in 0..blinks {
+        let mut next = HashMap::with_capacity(counts.len() * 2);
+        for (stone, n) in counts {
+            let (left, right) = blink(stone);
+            *next.entry(left).or_default() += n;
+            if let Some(right) = right {
+                *next.entry(right).or_default() += n;
+            }
+        }
+        counts = next;
+    }
+    counts.values().sum()
+}
+
+/// Stones blinking one blink at a time, for the stepper.
+#[derive(Clone)]
+struct Blinking(Vec<u64>);
+
+impl Stepper for Blinking {
+    fn step(&mut self) -> bool {
+        self.0 = self
+            .0
+            .iter()
+            .flat_map(|&stone| {
+                let (left, right) = blink(stone);
+                std::iter::once(left).chain(right)
+            })
+            .collect();
+        true
+    }
+
+    /// The number of stones and the first 100 of them.
+    fn render(&self) -> String {
+        let shown: Vec<_> = self.0.iter().take(100).map(|s| s.to_string()).collect();
+        let more = if self.0.len() > shown.len() {
+            " ..."
+        } else {
+            ""
+        };
+        format!("{} stones: {}{more}", self.0.len(), shown.join(" "))
+    }
+}
+
+fn part1(input: &[u64]) -> usize {
+    count_stones(input, 25)
+}
+
+fn part1_reference(mut input: Vec<u64>) -> usize {
+    for _ in 0..25 {
+        let mut next = Vec::with_capacity(input.len() * 2);
+        for stone in input.into_iter() {
+            let st = stone.to_string();
+            if stone == 0 {
+                next.push(1);
+            } else if (st.len() % 2) == 0 {
+                let mid = st.len() / 2;
+                let left = &st[0..mid];
+                let right = &st[mid..st.len()];
+                next.extend([left, right].map(|x| x.parse::<u64>().unwrap()));
+            } else {
+                next.push(stone * 2024);
+            }
+        }
+        input = next;
+    }
+    input.len()
+}
+
+fn part2(mut _input: Vec<u64>) -> usize {
+    0
+}
+
+/// A line of `size` stones.
+fn generate(rng: &mut Rng, size: usize) -> String {
+    let stones: Vec<_> = (0..size.max(1))
+        .map(|_| match rng.below(4) {
+            0 => 0,
+            _ => rng.gen_range(1..1_000_000),
+        })
+        .collect();
+    format!(
+        "{}\n",
+        stones
+            .iter()
+            .map(|s| s.to_string())
+            .collect::<Vec<_>>()
+            .join(" ")
+    )
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::examples;
+
+    #[test]
+    fn test_blinking() {
+        let mut stones = Blinking(vec![125, 17]);
+        stones.step();
+        stones.step();
+        assert_eq!(stones.render(), "4 stones: 253 0 2024 14168");
+        for _ in 0..4 {
+            stones.step();
+        }
+        assert_eq!(stones.0.len(), 22);
+    }
+
+    #[test]
+    fn test_part1() {
+        examples::check::<Day11>(1);
+    }
+}
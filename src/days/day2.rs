@@ -0,0 +1,241 @@
+use crate::parse::{self, ParseError};
+use crate::rng::Rng;
+use crate::solution::{Answer, Solution};
+use std::cmp::Ordering;
+
+pub struct Day2;
+
+impl Solution for Day2 {
+    const DAY: u32 = 2;
+    type Input = Vec<Vec<i64>>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1(input: &Self::Input) -> Answer {
+        part1(input).into()
+    }
+
+    fn part2(input: &Self::Input) -> Answer {
+        part2(input).into()
+    }
+
+    fn part2_reference(input: &Self::Input) -> Option<Answer> {
+        Some(part2_reference(input).into())
+    }
+
+    fn generate(rng: &mut Rng, size: usize) -> Option<String> {
+        Some(generate(rng, size))
+    }
+}
+
+fn parse_input(input: &str) -> Result<Vec<Vec<i64>>, ParseError> {
+    let mut res = vec![];
+    for line in parse::lines(input) {
+        let mut r = vec![];
+        for num in line.text.split_whitespace() {
+            r.push(line.parse(num, "a level")?);
+        }
+        if r.is_empty() {
+            return Err(line.error_at_end("a level"));
+        }
+        res.push(r);
+    }
+    Ok(res)
+}
+
+fn is_safe(line: &[i64], skip: usize) -> bool {
+    let mut it = line.iter().enumerate();
+    let mut inc = true;
+    let mut dec = true;
+    if skip == 0 {
+        let _ = it.next();
+    }
+    let Some((_, mut prev)) = it.next() else {
+        return true;
+    };
+    for (i, curr) in it {
+        if i == skip {
+            continue;
+        }
+        let diff = curr - prev;
+        if diff.abs() > 3 {
+            return false;
+        }
+        match diff.cmp(&0) {
+            Ordering::Greater => dec = false,
+            Ordering::Less => inc = false,
+            Ordering::Equal => return false,
+        }
+        prev = curr;
+    }
+    inc || dec
+}
+
+fn part1(input: &[Vec<i64>]) -> u64 {
+    input.iter().map(|x| is_safe(x, usize::MAX) as u64).sum()
+}
+
+/// Whether removing at most `k` levels makes `report` safe, in O(n·k).
+///
+/// For each direction, `removed[j]` is the fewest removals among the levels
+/// before `j` such that the kept ones up to and including `j` are safe. Only the
+/// `k + 1` levels before `j` can be the previous kept one, since skipping
+/// more would take more than `k` removals.
+pub fn is_safe_with_tolerance(report: &[i64], k: usize) -> bool {
+    let n = report.len();
+    // Removing more levels than there are makes no difference.
+    let k = k.min(n);
+    n == 0
+        || [1, -1].into_iter().any(|sign| {
+            let mut removed = vec![0; n];
+            for j in 0..n {
+                removed[j] = (j.saturating_sub(k + 1)..j)
+                    .filter(|&i| (1..=3).contains(&((report[j] - report[i]) * sign)))
+                    .map(|i| removed[i] + (j - i - 1))
+                    .fold(j, usize::min);
+            }
+            (0..n).any(|j| removed[j] + (n - 1 - j) <= k)
+        })
+}
+
+/// Number of reports made safe by removing at most `k` levels.
+pub fn count_safe(input: &[Vec<i64>], k: usize) -> u64 {
+    input
+        .iter()
+        .filter(|x| is_safe_with_tolerance(x, k))
+        .count() as u64
+}
+
+fn part2(input: &[Vec<i64>]) -> u64 {
+    count_safe(input, 1)
+}
+
+fn part2_reference(input: &[Vec<i64>]) -> u64 {
+    let mut res = 0;
+    for line in input {
+        let l = line.len();
+        for i in 0..l {
+            if is_safe(line, i) {
+                res += 1;
+                break;
+            }
+        }
+    }
+    res
+}
+
+/// `size` reports of 5 to 8 levels, mostly gradual with an occasional bad step.
+fn generate(rng: &mut Rng, size: usize) -> String {
+    let mut out = String::new();
+    for _ in 0..size {
+        let len = rng.gen_range(5..=8);
+        let inc = rng.chance(0.5);
+        let mut level = rng.gen_range(1..=90) as i64;
+        let mut report = vec![level.to_string()];
+        for _ in 1..len {
+            let mut step = rng.gen_range(1..=3) as i64;
+            if rng.chance(0.1) {
+                step = *rng.choose(&[0, 4, 5, -2]);
+            }
+            level += if inc { step } else { -step };
+            report.push(level.to_string());
+        }
+        out += &report.join(" ");
+        out.push('\n');
+    }
+    out
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::examples;
+
+    fn inp() -> &'static str {
+        examples::input(2, "example.txt")
+    }
+
+    #[test]
+    fn test_parse() {
+        let res = parse_input(inp()).unwrap();
+        assert_eq!(res.len(), 6);
+        assert_eq!(res[0], vec![7, 6, 4, 2, 1]);
+        assert_eq!(res[1], vec![1, 2, 7, 8, 9]);
+        assert_eq!(res[2], vec![9, 7, 6, 2, 1]);
+        assert_eq!(res[3], vec![1, 3, 2, 4, 5]);
+        assert_eq!(res[4], vec![8, 6, 4, 4, 1]);
+        assert_eq!(res[5], vec![1, 3, 6, 7, 9]);
+    }
+
+    #[test]
+    fn test_is_safe() {
+        let i = parse_input(inp()).unwrap();
+        let res: Vec<bool> = i.iter().map(|x| is_safe(x, usize::MAX)).collect();
+        assert!(res[0]);
+        assert!(!res[1]);
+        assert!(!res[2]);
+        assert!(!res[3]);
+        assert!(!res[4]);
+        assert!(res[5]);
+    }
+
+    #[test]
+    fn test_is_safe_skipped() {
+        let i = parse_input(inp()).unwrap();
+        let res: Vec<bool> = vec![is_safe(&i[3], 1), is_safe(&i[4], 2)];
+        assert!(res[0]);
+        assert!(res[1]);
+    }
+
+    #[test]
+    fn test_is_safe_with_tolerance() {
+        // Whether some choice of at most `k` levels to remove leaves a safe report.
+        fn brute_force(report: &[i64], k: usize) -> bool {
+            (0u32..1 << report.len())
+                .filter(|mask| mask.count_ones() as usize <= k)
+                .any(|mask| {
+                    let kept: Vec<_> = (0..report.len())
+                        .filter(|&i| mask & 1 << i == 0)
+                        .map(|i| report[i])
+                        .collect();
+                    is_safe(&kept, usize::MAX)
+                })
+        }
+        let mut rng = Rng::new(3);
+        for _ in 0..500 {
+            let len = rng.gen_range(0..=9) as usize;
+            let report: Vec<_> = (0..len).map(|_| rng.gen_range(1..=12) as i64).collect();
+            for k in 0..=3 {
+                assert_eq!(
+                    is_safe_with_tolerance(&report, k),
+                    brute_force(&report, k),
+                    "{report:?} with tolerance {k}"
+                );
+            }
+        }
+        assert!(!is_safe_with_tolerance(&[1, 9, 9, 2, 3], 1));
+        assert!(is_safe_with_tolerance(&[1, 9, 9, 2, 3], 2));
+        assert!(is_safe_with_tolerance(&[1, 9, 9, 2, 3], usize::MAX));
+        assert!(is_safe_with_tolerance(&[7], usize::MAX));
+    }
+
+    #[test]
+    fn test_count_safe() {
+        let i = parse_input(inp()).unwrap();
+        assert_eq!(count_safe(&i, 0), 2);
+        assert_eq!(count_safe(&i, 1), 4);
+        assert_eq!(count_safe(&i, 5), 6);
+    }
+
+    #[test]
+    fn test_part1() {
+        examples::check::<Day2>(1);
+    }
+
+    #[test]
+    fn test_part2() {
+        examples::check::<Day2>(2);
+    }
+}
@@ -0,0 +1,180 @@
+use crate::parse::ParseError;
+use crate::rng::Rng;
+use crate::solution::{Answer, Solution};
+use regex::Regex;
+
+#[derive(Eq, PartialEq, Debug)]
+pub enum Ins {
+    Mul(u64, u64),
+    Do,
+    Dont,
+}
+
+pub struct Day3;
+
+impl Solution for Day3 {
+    const DAY: u32 = 3;
+    type Input = Vec<Ins>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1(input: &Self::Input) -> Answer {
+        part1(input).into()
+    }
+
+    fn part2(input: &Self::Input) -> Answer {
+        part2(input).into()
+    }
+
+    fn generate(rng: &mut Rng, size: usize) -> Option<String> {
+        Some(generate(rng, size))
+    }
+}
+
+fn parse_input(input: &str) -> Result<Vec<Ins>, ParseError> {
+    let re = Regex::new(r"mul\(([[:digit:]]+),([[:digit:]]+)\)|(d)(o)\(\)|do(n)'(t)\(\)").unwrap();
+    let mut res = vec![];
+    for mat in re.captures_iter(input) {
+        let (all, [l, r]) = mat.extract();
+
+        if all.starts_with('m') {
+            let num = |n: &str| n.parse().map_err(|_| ParseError::at(input, n, "a factor"));
+            res.push(Ins::Mul(num(l)?, num(r)?));
+        } else if r == "t" {
+            res.push(Ins::Dont);
+        } else {
+            res.push(Ins::Do);
+        }
+    }
+    Ok(res)
+}
+
+fn part1(input: &[Ins]) -> u64 {
+    input
+        .iter()
+        .map(|ins| match ins {
+            Ins::Mul(l, r) => l * r,
+            Ins::Do | Ins::Dont => 0,
+        })
+        .sum()
+}
+
+fn part2(input: &[Ins]) -> u64 {
+    let mut enabled = true;
+    let mut res = 0;
+    for ins in input {
+        match ins {
+            Ins::Do => enabled = true,
+            Ins::Dont => enabled = false,
+            Ins::Mul(l, r) => res += enabled as u64 * l * r,
+        }
+    }
+    res
+}
+
+/// Corrupted memory with `size` instructions, valid or not, between random noise.
+fn generate(rng: &mut Rng, size: usize) -> String {
+    const NOISE: &[&str] = &[
+        "mul[3,7]",
+        "mul(4*",
+        "mul ( 2 , 4 )",
+        "mul(6,9!",
+        "?(12,34)",
+        "don't",
+        "do(",
+        "from()",
+        "select()",
+        "what()",
+        "how(",
+        "when()",
+        "{",
+        "}",
+        "'",
+        "%",
+        "&",
+        "@",
+        "^",
+        "+",
+        "-",
+        "<",
+        ">",
+        "!",
+        "#",
+        "$",
+        " ",
+        "[",
+        "]",
+        ",",
+        ")",
+        "(",
+        "*",
+        "/",
+        ":",
+        ";",
+        "~",
+    ];
+    let mut out = String::new();
+    for i in 0..size {
+        if i > 0 && i % 60 == 0 {
+            out.push('\n');
+        }
+        for _ in 0..rng.gen_range(0..=3) {
+            out += *rng.choose(NOISE);
+        }
+        match rng.below(10) {
+            0 => out += "do()",
+            1 => out += "don't()",
+            _ => out += &format!("mul({},{})", rng.gen_range(1..1000), rng.gen_range(1..1000)),
+        }
+    }
+    out.push('\n');
+    out
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::examples;
+
+    fn inp1() -> &'static str {
+        examples::input(3, "part1.txt")
+    }
+
+    fn inp2() -> &'static str {
+        examples::input(3, "part2.txt")
+    }
+
+    #[test]
+    fn test_parse1() {
+        let res = parse_input(inp1()).unwrap();
+        assert_eq!(res.len(), 4);
+        assert_eq!(res[0], Ins::Mul(2, 4));
+        assert_eq!(res[1], Ins::Mul(5, 5));
+        assert_eq!(res[2], Ins::Mul(11, 8));
+        assert_eq!(res[3], Ins::Mul(8, 5));
+    }
+
+    #[test]
+    fn test_parse2() {
+        let res = parse_input(inp2()).unwrap();
+        assert_eq!(res.len(), 6);
+        assert_eq!(res[0], Ins::Mul(2, 4));
+        assert_eq!(res[1], Ins::Dont);
+        assert_eq!(res[2], Ins::Mul(5, 5));
+        assert_eq!(res[3], Ins::Mul(11, 8));
+        assert_eq!(res[4], Ins::Do);
+        assert_eq!(res[5], Ins::Mul(8, 5));
+    }
+
+    #[test]
+    fn test_part1() {
+        examples::check::<Day3>(1);
+    }
+
+    #[test]
+    fn test_part2() {
+        examples::check::<Day3>(2);
+    }
+}
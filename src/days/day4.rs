@@ -0,0 +1,131 @@
+use crate::geometry::Direction8;
+use crate::grid::Grid;
+use crate::parse::ParseError;
+use crate::rng::Rng;
+use crate::solution::{Answer, Solution};
+
+pub struct Day4;
+
+impl Solution for Day4 {
+    const DAY: u32 = 4;
+    type Input = Grid<char>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1(input: &Self::Input) -> Answer {
+        part1(input).into()
+    }
+
+    fn part2(input: &Self::Input) -> Answer {
+        part2(input).into()
+    }
+
+    fn generate(rng: &mut Rng, size: usize) -> Option<String> {
+        Some(generate(rng, size))
+    }
+}
+
+fn parse_input(input: &str) -> Result<Grid<char>, ParseError> {
+    Grid::parse(input, Ok)
+}
+
+fn part1(input: &Grid<char>) -> u64 {
+    const PATTERN: &str = "XMAS";
+    let mut res = 0;
+    for (pos, &ch) in input.iter() {
+        if ch != PATTERN.chars().next().unwrap() {
+            continue;
+        }
+        for dir in Direction8::ALL {
+            if input
+                .ray(pos, dir.vec())
+                .map(|p| input[p])
+                .take(PATTERN.len())
+                .eq(PATTERN.chars())
+            {
+                res += 1;
+            }
+        }
+    }
+    res
+}
+
+fn part2(input: &Grid<char>) -> u64 {
+    let mut res = 0;
+    for (pos, &ch) in input.iter() {
+        if ch != 'A' {
+            continue;
+        }
+        let corner = |dir: Direction8| input.step(pos, dir.vec()).map(|p| input[p]);
+        let (Some(top_left), Some(top_right), Some(bot_left), Some(bot_right)) = (
+            corner(Direction8::UpLeft),
+            corner(Direction8::UpRight),
+            corner(Direction8::DownLeft),
+            corner(Direction8::DownRight),
+        ) else {
+            continue;
+        };
+        let diag1 =
+            ((top_left == 'M') && (bot_right == 'S')) || ((top_left == 'S') && (bot_right == 'M'));
+        let diag2 =
+            ((bot_left == 'M') && (top_right == 'S')) || ((bot_left == 'S') && (top_right == 'M'));
+        if diag1 && diag2 {
+            res += 1;
+        }
+    }
+
+    res
+}
+
+/// A `size` by `size` word search over the letters of `XMAS`.
+fn generate(rng: &mut Rng, size: usize) -> String {
+    let size = size.max(1);
+    let mut out = String::new();
+    for _ in 0..size {
+        for _ in 0..size {
+            out.push(*rng.choose(&['X', 'M', 'A', 'S']));
+        }
+        out.push('\n');
+    }
+    out
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::examples;
+
+    fn inp() -> &'static str {
+        examples::input(4, "example.txt")
+    }
+
+    #[test]
+    fn test_parse() {
+        let parsed = parse_input(inp()).unwrap();
+        assert_eq!(parsed.height(), 10);
+        assert_eq!(parsed.width(), 10);
+        let rows: Vec<String> = parsed.rows().map(|r| r.iter().collect()).collect();
+        assert_eq!(rows[0], "MMMSXXMASM");
+        assert_eq!(rows[1], "MSAMXMSMSA");
+        assert_eq!(rows[2], "AMXSXMAAMM");
+        assert_eq!(rows[3], "MSAMASMSMX");
+        assert_eq!(rows[4], "XMASAMXAMM");
+        assert_eq!(rows[5], "XXAMMXXAMA");
+        assert_eq!(rows[6], "SMSMSASXSS");
+        assert_eq!(rows[7], "SAXAMASAAA");
+        assert_eq!(rows[8], "MAMMMXMMMM");
+        assert_eq!(rows[9], "MXMXAXMASX");
+    }
+
+    #[test]
+    fn test_part1() {
+        examples::check::<Day4>(1);
+    }
+
+    #[test]
+    fn test_part2() {
+        examples::check::<Day4>(2);
+    }
+}
@@ -0,0 +1,236 @@
+use crate::parse::{self, ParseError};
+use crate::rng::Rng;
+use crate::solution::{Answer, Solution};
+use std::cmp;
+use std::collections::{HashMap, HashSet};
+
+type Rule = (u64, u64);
+type Update = Vec<u64>;
+
+#[derive(PartialEq)]
+enum Ordering {
+    Correct,
+    Wrong(usize, usize),
+}
+
+pub struct Day5;
+
+impl Solution for Day5 {
+    const DAY: u32 = 5;
+    type Input = (HashMap<u64, Vec<u64>>, Vec<Vec<u64>>);
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        let (rules, pages) = parse_input(input)?;
+        Ok((mapify_rules(&rules), pages))
+    }
+
+    fn part1((rules, pages): &Self::Input) -> Answer {
+        part1(rules, pages).into()
+    }
+
+    fn part2((rules, pages): &Self::Input) -> Answer {
+        part2(rules, &mut pages.clone()).into()
+    }
+
+    fn part2_reference((rules, pages): &Self::Input) -> Option<Answer> {
+        Some(part2_reference(rules, &mut pages.clone()).into())
+    }
+
+    fn generate(rng: &mut Rng, size: usize) -> Option<String> {
+        Some(generate(rng, size))
+    }
+}
+
+fn parse_input(input: &str) -> Result<(Vec<Rule>, Vec<Update>), ParseError> {
+    let mut rules = vec![];
+    let mut pages = vec![];
+
+    let mut step1 = true;
+    for line in parse::lines(input) {
+        if step1 {
+            if line.text.is_empty() {
+                step1 = false;
+                continue;
+            }
+            let mut s = line.text.split('|');
+            let l = line.parse_next(&mut s, "a page number")?;
+            let r = line.parse_next(&mut s, "'|' and a page number")?;
+            line.end(&mut s)?;
+            rules.push((l, r));
+        } else {
+            let p = line
+                .text
+                .split(',')
+                .map(|x| line.parse(x, "a page number"))
+                .collect::<Result<_, _>>()?;
+            pages.push(p);
+        }
+    }
+
+    Ok((rules, pages))
+}
+
+fn mapify_rules(rules: &[(u64, u64)]) -> HashMap<u64, Vec<u64>> {
+    let mut res = HashMap::new();
+    for r in rules {
+        res.entry(r.0)
+            .and_modify(|x: &mut Vec<u64>| x.push(r.1))
+            .or_insert(vec![r.1]);
+    }
+    res
+}
+
+fn correct_ordering(update: &[u64], rules: &HashMap<u64, Vec<u64>>) -> Ordering {
+    let mut seen_pages = HashSet::new();
+    for (i, x) in update.iter().enumerate() {
+        let r = &rules.get(x);
+        if r.is_some() {
+            let r = r.unwrap();
+            for y in r {
+                if seen_pages.contains(y) {
+                    for (j, z) in update.iter().enumerate() {
+                        if y == z {
+                            return Ordering::Wrong(i, j);
+                        }
+                    }
+                    unreachable!();
+                }
+            }
+        }
+        seen_pages.insert(x);
+    }
+    Ordering::Correct
+}
+
+fn part1(rules: &HashMap<u64, Vec<u64>>, pages: &[Vec<u64>]) -> u64 {
+    let mut res = 0;
+    for p in pages {
+        if correct_ordering(p, rules) == Ordering::Correct {
+            let middle = p.len() / 2;
+            res += p[middle];
+        }
+    }
+    res
+}
+
+/// Orders two pages by the rule between them, if there is one.
+fn compare(rules: &HashMap<u64, Vec<u64>>, a: u64, b: u64) -> cmp::Ordering {
+    let before = |x: u64, y: u64| rules.get(&x).is_some_and(|r| r.contains(&y));
+    if before(a, b) {
+        cmp::Ordering::Less
+    } else if before(b, a) {
+        cmp::Ordering::Greater
+    } else {
+        cmp::Ordering::Equal
+    }
+}
+
+fn part2(rules: &HashMap<u64, Vec<u64>>, pages: &mut [Vec<u64>]) -> u64 {
+    let mut res = 0;
+    for p in pages {
+        if correct_ordering(p, rules) == Ordering::Correct {
+            continue;
+        }
+        p.sort_by(|&a, &b| compare(rules, a, b));
+        res += p[p.len() / 2];
+    }
+    res
+}
+
+fn part2_reference(rules: &HashMap<u64, Vec<u64>>, pages: &mut [Vec<u64>]) -> u64 {
+    let mut res = 0;
+    for p in pages {
+        let mut was_wrong = false;
+        loop {
+            match correct_ordering(p, rules) {
+                Ordering::Correct => break,
+                Ordering::Wrong(a, b) => {
+                    was_wrong = true;
+                    p.swap(a, b)
+                }
+            }
+        }
+        if was_wrong {
+            let middle = p.len() / 2;
+            res += p[middle];
+        }
+    }
+    res
+}
+
+/// Rules totally ordering `size` pages, followed by `size` updates of odd length.
+fn generate(rng: &mut Rng, size: usize) -> String {
+    let mut pages: Vec<u64> = (10..100).collect();
+    rng.shuffle(&mut pages);
+    pages.truncate(size.clamp(3, pages.len()));
+
+    let mut rules = Vec::new();
+    for (i, l) in pages.iter().enumerate() {
+        for r in &pages[i + 1..] {
+            rules.push(format!("{l}|{r}"));
+        }
+    }
+    rng.shuffle(&mut rules);
+
+    let mut out = rules.join("\n");
+    out += "\n\n";
+    for _ in 0..size.max(1) {
+        let max_len = pages.len().min(23) as u64;
+        let len = rng.gen_range(2..=max_len.div_ceil(2)) * 2 - 1;
+        let mut update = pages.clone();
+        rng.shuffle(&mut update);
+        update.truncate(len as usize);
+        if rng.chance(0.5) {
+            // Restore the rule order, so roughly half the updates are correct.
+            update.sort_by_key(|p| pages.iter().position(|x| x == p));
+        }
+        out += &update
+            .iter()
+            .map(|p| p.to_string())
+            .collect::<Vec<_>>()
+            .join(",");
+        out.push('\n');
+    }
+    out
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::examples;
+
+    fn inp() -> &'static str {
+        examples::input(5, "example.txt")
+    }
+
+    #[test]
+    fn test_parse() {
+        let res = parse_input(inp()).unwrap();
+        assert_eq!(res.0.len(), 21);
+        assert_eq!(res.0[0], (47, 53));
+        assert_eq!(res.1.len(), 6);
+        assert_eq!(res.1[5], vec![97, 13, 75, 29, 47]);
+    }
+
+    #[test]
+    fn test_mapify() {
+        let (i, _) = parse_input(inp()).unwrap();
+        let res = mapify_rules(&i);
+        assert_eq!(res[&97], vec![13, 61, 47, 29, 53, 75]);
+        assert_eq!(res[&75], vec![29, 53, 47, 61, 13]);
+        assert_eq!(res[&47], vec![53, 13, 61, 29]);
+        assert_eq!(res[&29], vec![13]);
+        assert_eq!(res[&53], vec![29, 13]);
+        assert_eq!(res[&61], vec![13, 53, 29]);
+    }
+
+    #[test]
+    fn test_part1() {
+        examples::check::<Day5>(1);
+    }
+
+    #[test]
+    fn test_part2() {
+        examples::check::<Day5>(2);
+    }
+}
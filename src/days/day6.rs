@@ -0,0 +1,409 @@
+use crate::cycle;
+use crate::geometry::{Direction4, Point};
+use crate::grid::Grid;
+use crate::parse::ParseError;
+use crate::render::{self, Color, Frames};
+use crate::rng::Rng;
+use crate::solution::{Answer, Solution};
+use crate::stepper::Stepper;
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::fmt;
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+struct GuardState {
+    dir: Direction4,
+    pos: Point,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Tile {
+    Free,
+    Blocked,
+    Visited,
+}
+
+#[derive(Clone, Debug)]
+pub struct State {
+    map: Grid<Tile>,
+    guard: GuardState,
+}
+
+impl State {
+    /// Where the guard goes next on `map`, `None` once they leave it.
+    fn next_guard(map: &Grid<Tile>, guard: GuardState) -> Option<GuardState> {
+        let GuardState { pos, dir } = guard;
+        let ahead = map.step(pos, dir.vec())?;
+        Some(match map[ahead] {
+            Tile::Blocked => GuardState {
+                pos,
+                dir: dir.turn_right(),
+            },
+            Tile::Free | Tile::Visited => GuardState { pos: ahead, dir },
+        })
+    }
+
+    fn step(&mut self) -> Option<GuardState> {
+        self.guard = Self::next_guard(&self.map, self.guard)?;
+        self.map[self.guard.pos] = Tile::Visited;
+
+        Some(self.guard)
+    }
+
+    /// The map with visited tiles highlighted and the guard in red.
+    fn colors(&self) -> Grid<Color> {
+        let mut colors = self.map.map(|t| match t {
+            Tile::Free => render::DARK,
+            Tile::Blocked => render::GREY,
+            Tile::Visited => render::BLUE,
+        });
+        colors[self.guard.pos] = render::RED;
+        colors
+    }
+
+    /// Whether the guard walks in circles instead of leaving the map.
+    fn loops(&self) -> bool {
+        cycle::brent(self.guard, |&g| Self::next_guard(&self.map, g)).is_some()
+    }
+}
+
+impl fmt::Display for State {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut out = "".to_string();
+        for (y, row) in self.map.rows().enumerate() {
+            for (x, c) in row.iter().enumerate() {
+                if self.guard.pos == Point::new(x, y) {
+                    match self.guard.dir {
+                        Direction4::Up => out.push('^'),
+                        Direction4::Down => out.push('v'),
+                        Direction4::Left => out.push('<'),
+                        Direction4::Right => out.push('>'),
+                    }
+                } else {
+                    match c {
+                        Tile::Free => out.push('.'),
+                        Tile::Blocked => out.push('#'),
+                        Tile::Visited => out.push('X'),
+                    }
+                }
+            }
+            out.push('\n');
+        }
+        write!(f, "{out}")
+    }
+}
+
+/// Most recent guard states a [`Walk`] keeps for `history`.
+const HISTORY_LEN: usize = 1000;
+
+/// The guard's walk for the stepper, remembering where they have been.
+#[derive(Clone)]
+struct Walk {
+    state: State,
+    /// Steps taken so far.
+    steps: usize,
+    /// The last [`HISTORY_LEN`] guard states, the current one last.
+    history: VecDeque<GuardState>,
+    /// Iteration at which each guard state was first seen since the map last changed.
+    seen: HashMap<GuardState, usize>,
+}
+
+impl Walk {
+    fn new(state: State) -> Self {
+        Self {
+            steps: 0,
+            history: VecDeque::from([state.guard]),
+            seen: HashMap::from([(state.guard, 0)]),
+            state,
+        }
+    }
+
+    fn toggle(&mut self, x: &str, y: &str) -> Result<String, String> {
+        let coordinate = |c: &str| c.parse().map_err(|_| format!("{c} is not a coordinate"));
+        let pos = Point::new(coordinate(x)?, coordinate(y)?);
+        let Some(&tile) = self.state.map.get(pos) else {
+            return Err(format!("{pos} is not on the map"));
+        };
+        if pos == self.state.guard.pos {
+            return Err(format!("The guard is standing on {pos}"));
+        }
+        let (tile, message) = match tile {
+            Tile::Blocked => (Tile::Free, "Removed the obstacle at"),
+            Tile::Free | Tile::Visited => (Tile::Blocked, "Placed an obstacle at"),
+        };
+        self.state.map[pos] = tile;
+        // Earlier guard states were on a different map, so they no longer prove a loop.
+        self.seen = HashMap::from([(self.state.guard, self.steps)]);
+        Ok(format!("{message} {pos}"))
+    }
+
+    fn history(&self, n: &str) -> Result<String, String> {
+        let n: usize = n.parse().map_err(|_| format!("{n} is not a number"))?;
+        let skip = self.history.len().saturating_sub(n);
+        // The oldest guard state kept is from this iteration.
+        let first = self.steps + 1 - self.history.len();
+        Ok(self
+            .history
+            .iter()
+            .enumerate()
+            .skip(skip)
+            .map(|(i, g)| format!("{:>6}: {} facing {:?}", first + i, g.pos, g.dir))
+            .collect::<Vec<_>>()
+            .join("\n"))
+    }
+}
+
+impl Stepper for Walk {
+    fn step(&mut self) -> bool {
+        let Some(guard) = self.state.step() else {
+            return false;
+        };
+        self.steps += 1;
+        self.seen.entry(guard).or_insert(self.steps);
+        if self.history.len() == HISTORY_LEN {
+            self.history.pop_front();
+        }
+        self.history.push_back(guard);
+        true
+    }
+
+    fn looping(&self) -> bool {
+        self.seen[&self.state.guard] < self.steps
+    }
+
+    fn render(&self) -> String {
+        let GuardState { pos, dir } = self.state.guard;
+        let mut out = format!("{}Guard at {pos} facing {dir:?}", self.state);
+        if self.looping() {
+            let first = self.seen[&self.state.guard];
+            out += &format!(", walking in circles since iteration {first}");
+        }
+        out
+    }
+
+    fn command(&mut self, words: &[&str]) -> Option<Result<String, String>> {
+        match words {
+            ["obstacle" | "o", x, y] => Some(self.toggle(x, y)),
+            ["history"] => Some(self.history("10")),
+            ["history", n] => Some(self.history(n)),
+            _ => None,
+        }
+    }
+
+    fn help(&self) -> &'static str {
+        "\
+obstacle <x> <y>, o  place or remove an obstacle
+history [n]       list the last n guard positions, up to 1000
+"
+    }
+}
+
+pub struct Day6;
+
+impl Solution for Day6 {
+    const DAY: u32 = 6;
+    type Input = State;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1(input: &Self::Input) -> Answer {
+        part1(input.clone()).into()
+    }
+
+    fn part2(input: &Self::Input) -> Answer {
+        part2(input.clone()).into()
+    }
+
+    fn part2_reference(input: &Self::Input) -> Option<Answer> {
+        Some(part2_reference(input.clone()).into())
+    }
+
+    fn generate(rng: &mut Rng, size: usize) -> Option<String> {
+        Some(generate(rng, size))
+    }
+
+    /// The guard's walk, one frame per step, until they leave or start going in circles.
+    fn frames(input: &Self::Input) -> Option<Frames<'_>> {
+        let mut state = input.clone();
+        let mut seen = HashSet::from([state.guard]);
+        let start = state.colors();
+        let walk = std::iter::from_fn(move || {
+            let guard = state.step()?;
+            seen.insert(guard).then(|| state.colors())
+        });
+        Some(Box::new(std::iter::once(start).chain(walk)))
+    }
+
+    fn stepper(input: &Self::Input) -> Option<Box<dyn Stepper>> {
+        Some(Box::new(Walk::new(input.clone())))
+    }
+}
+
+fn parse_input(input: &str) -> Result<State, ParseError> {
+    let chars = Grid::parse(input, |c| match c {
+        '.' | '#' | '^' | 'v' | '>' | '<' => Ok(c),
+        _ => Err("'.', '#' or a guard"),
+    })?;
+    let guard_pos = chars
+        .find(|c| matches!(c, '^' | 'v' | '>' | '<'))
+        .ok_or_else(|| ParseError::at(input, &input[input.len()..], "a guard"))?;
+    let guard_dir = match chars.get(guard_pos) {
+        Some('v') => Direction4::Down,
+        Some('>') => Direction4::Right,
+        Some('<') => Direction4::Left,
+        _ => Direction4::Up,
+    };
+    let map = chars.map(|c| match c {
+        '.' => Tile::Free,
+        '#' => Tile::Blocked,
+        _ => Tile::Visited,
+    });
+
+    Ok(State {
+        map,
+        guard: GuardState {
+            pos: guard_pos,
+            dir: guard_dir,
+        },
+    })
+}
+
+fn part1(mut input: State) -> u64 {
+    while input.step().is_some() {}
+
+    input
+        .map
+        .iter()
+        .filter(|(_, &t)| t == Tile::Visited)
+        .count() as u64
+}
+
+fn part2(mut input: State) -> u64 {
+    let mut res = 0;
+
+    for pos in input.map.positions().collect::<Vec<_>>() {
+        if input.map[pos] != Tile::Free {
+            continue;
+        }
+        input.map[pos] = Tile::Blocked;
+        if input.loops() {
+            res += 1;
+        }
+        input.map[pos] = Tile::Free;
+    }
+
+    res
+}
+
+/// [`part2`] by remembering every state of the guard until one repeats.
+fn part2_reference(input: State) -> u64 {
+    let mut res = 0;
+
+    for pos in input.map.positions() {
+        let mut state = input.clone();
+        if state.map[pos] == Tile::Free {
+            state.map[pos] = Tile::Blocked;
+        } else {
+            continue;
+        }
+        let mut seen = HashSet::new();
+        while let Some(guard) = state.step() {
+            if seen.contains(&guard) {
+                res += 1;
+                break;
+            }
+            seen.insert(guard);
+        }
+    }
+
+    res
+}
+
+/// A `size` by `size` lab map with scattered obstructions and a guard that walks out.
+fn generate(rng: &mut Rng, size: usize) -> String {
+    let size = size.max(1);
+    loop {
+        let mut map = Grid::from_vec(size, size, vec!['.'; size * size]);
+        for pos in map.positions().collect::<Vec<_>>() {
+            if rng.chance(0.04) {
+                map[pos] = '#';
+            }
+        }
+        let guard = Point::new(rng.index(size), rng.index(size));
+        map[guard] = *rng.choose(&['^', 'v', '<', '>']);
+
+        // Part 1 needs the guard to leave the map, so retry until it does.
+        let out = map.to_string();
+        if !parse_input(&out).unwrap().loops() {
+            return out;
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::examples;
+
+    #[test]
+    fn test_parse_error() {
+        let err = parse_input("..#\n.?^\n").unwrap_err();
+        assert_eq!((err.line, err.column, err.text.as_str()), (2, 2, "?"));
+        let err = parse_input("..#\n...\n").unwrap_err();
+        assert_eq!(err.expected, "a guard");
+    }
+
+    #[test]
+    fn test_stepper() {
+        let input = parse_input(&examples::load(6).unwrap()[0].input).unwrap();
+        let mut walk = Walk::new(input);
+        assert_eq!(
+            walk.command(&["o", "4", "6"]),
+            Some(Err("The guard is standing on (4, 6)".into()))
+        );
+        assert_eq!(
+            walk.command(&["o", "3", "6"]),
+            Some(Ok("Placed an obstacle at (3, 6)".into()))
+        );
+        for _ in 0..40 {
+            assert!(walk.step());
+        }
+        assert!(walk.looping());
+        assert!(walk.render().contains("walking in circles since iteration"));
+        assert_eq!(
+            walk.command(&["history", "2"])
+                .unwrap()
+                .unwrap()
+                .lines()
+                .count(),
+            2
+        );
+        // Only the latest guard states are kept, numbered by their iteration.
+        for _ in 0..2000 {
+            assert!(walk.step());
+        }
+        let history = walk.command(&["history", "5000"]).unwrap().unwrap();
+        assert_eq!(history.lines().count(), HISTORY_LEN);
+        assert!(history.lines().last().unwrap().starts_with("  2040:"));
+    }
+
+    #[test]
+    fn test_part1() {
+        examples::check::<Day6>(1);
+    }
+
+    #[test]
+    fn test_part2() {
+        examples::check::<Day6>(2);
+    }
+
+    #[test]
+    fn test_reference() {
+        let mut rng = Rng::new(6);
+        for size in [1, 5, 12, 30] {
+            let input = parse_input(&generate(&mut rng, size)).unwrap();
+            assert_eq!(part2(input.clone()), part2_reference(input));
+        }
+    }
+}
@@ -0,0 +1,237 @@
+use crate::parse::{self, ParseError};
+use crate::rng::Rng;
+use crate::solution::{Answer, Solution};
+use itertools::Itertools;
+use std::fmt;
+
+#[derive(Clone, Debug)]
+pub struct Equation {
+    result: i64,
+    numbers: Vec<i64>,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+enum Operator {
+    Add,
+    Mul,
+    Cat,
+}
+
+impl fmt::Display for Operator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let out = match *self {
+            Operator::Add => "+",
+            Operator::Mul => "*",
+            Operator::Cat => "||",
+        };
+        write!(f, "{out}")
+    }
+}
+
+const ADD_MUL: [Operator; 2] = [Operator::Add, Operator::Mul];
+const ALL_OPS: [Operator; 3] = [Operator::Add, Operator::Mul, Operator::Cat];
+
+/// Power of ten that shifts a number left by as many digits as `right` has.
+fn cat_multiplicator(right: i64) -> i64 {
+    let mut multiplicator = 10;
+    while multiplicator <= right {
+        multiplicator *= 10;
+    }
+    multiplicator
+}
+
+#[derive(Debug)]
+struct Calculator {
+    accumulator: i64,
+}
+
+impl Calculator {
+    fn new(num: i64) -> Self {
+        Self { accumulator: num }
+    }
+
+    fn apply(&mut self, op: Operator, num: i64) {
+        let new = match op {
+            Operator::Add => self.accumulator + num,
+            Operator::Mul => self.accumulator * num,
+            Operator::Cat => self.accumulator * cat_multiplicator(num) + num,
+        };
+        self.accumulator = new;
+    }
+}
+
+pub struct Day7;
+
+impl Solution for Day7 {
+    const DAY: u32 = 7;
+    type Input = Vec<Equation>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1(input: &Self::Input) -> Answer {
+        part1(input).into()
+    }
+
+    fn part2(input: &Self::Input) -> Answer {
+        part2(input).into()
+    }
+
+    fn part1_reference(input: &Self::Input) -> Option<Answer> {
+        Some(part1_reference(input.clone()).into())
+    }
+
+    fn part2_reference(input: &Self::Input) -> Option<Answer> {
+        Some(part2_reference(input.clone()).into())
+    }
+
+    fn generate(rng: &mut Rng, size: usize) -> Option<String> {
+        Some(generate(rng, size))
+    }
+}
+
+fn parse_input(input: &str) -> Result<Vec<Equation>, ParseError> {
+    let mut res = Vec::new();
+    for l in parse::lines(input) {
+        let mut splitted = l.text.split(':');
+        let result = l.parse_next(&mut splitted, "a test value")?;
+        let numbers = l
+            .next(&mut splitted, "':' and numbers")?
+            .trim()
+            .split(' ')
+            .map(|n| l.parse(n, "a number"))
+            .collect::<Result<_, _>>()?;
+        l.end(&mut splitted)?;
+        res.push(Equation { result, numbers });
+    }
+    Ok(res)
+}
+
+fn check_eq(eq: Equation, allowed_ops: &[Operator]) -> bool {
+    let num_ops = eq.numbers.len() - 1;
+    for ops in (0..num_ops).map(|_| allowed_ops).multi_cartesian_product() {
+        let mut nums = eq.numbers.iter();
+        let first = nums.next().unwrap();
+        let mut cal = Calculator::new(*first);
+        for o in ops.iter() {
+            let right = nums.next().unwrap();
+            cal.apply(**o, *right)
+        }
+        if cal.accumulator == eq.result {
+            return true;
+        }
+    }
+    false
+}
+
+/// Whether `numbers` can be combined into `result` using `allowed_ops`.
+///
+/// Works backwards from the last number, undoing each operator and dropping
+/// the ones that cannot have produced the current value.
+fn solvable(result: i64, numbers: &[i64], allowed_ops: &[Operator]) -> bool {
+    let Some((&last, rest)) = numbers.split_last() else {
+        return false;
+    };
+    if rest.is_empty() {
+        return result == last;
+    }
+    allowed_ops.iter().any(|op| match op {
+        Operator::Add => solvable(result - last, rest, allowed_ops),
+        Operator::Mul => {
+            if last == 0 {
+                result == 0
+            } else {
+                result % last == 0 && solvable(result / last, rest, allowed_ops)
+            }
+        }
+        Operator::Cat => {
+            let multiplicator = cat_multiplicator(last);
+            (result - last) % multiplicator == 0
+                && solvable((result - last) / multiplicator, rest, allowed_ops)
+        }
+    })
+}
+
+fn total(input: &[Equation], allowed_ops: &[Operator]) -> i64 {
+    input
+        .iter()
+        .filter(|eq| solvable(eq.result, &eq.numbers, allowed_ops))
+        .map(|eq| eq.result)
+        .sum()
+}
+
+fn part1(input: &[Equation]) -> i64 {
+    total(input, &ADD_MUL)
+}
+
+fn part2(input: &[Equation]) -> i64 {
+    total(input, &ALL_OPS)
+}
+
+fn part1_reference(input: Vec<Equation>) -> i64 {
+    let mut res = 0;
+    for eq in input {
+        let eq_res = eq.result;
+        if check_eq(eq, &ADD_MUL) {
+            res += eq_res;
+        }
+    }
+    res
+}
+
+fn part2_reference(input: Vec<Equation>) -> i64 {
+    let mut res = 0;
+    for eq in input {
+        let eq_res = eq.result;
+        if check_eq(eq, &ALL_OPS) {
+            res += eq_res;
+        }
+    }
+    res
+}
+
+/// `size` equations, about half of which can be made true.
+fn generate(rng: &mut Rng, size: usize) -> String {
+    let mut out = String::new();
+    for _ in 0..size {
+        let len = rng.gen_range(2..=12);
+        // Keep at most 18 digits in total, so no combination of operators overflows.
+        let max = 10u64.pow((18 / len).min(3) as u32);
+        let numbers: Vec<i64> = (0..len).map(|_| rng.gen_range(1..max) as i64).collect();
+        let mut cal = Calculator::new(numbers[0]);
+        for &n in &numbers[1..] {
+            cal.apply(*rng.choose(&ALL_OPS), n);
+        }
+        let result = if rng.chance(0.5) {
+            cal.accumulator
+        } else {
+            cal.accumulator + 1
+        };
+        out += &format!("{result}: {}\n", numbers.iter().join(" "));
+    }
+    out
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::examples;
+
+    #[test]
+    fn test_part1() {
+        examples::check::<Day7>(1);
+    }
+
+    #[test]
+    fn test_part2() {
+        examples::check::<Day7>(2);
+    }
+
+    #[test]
+    fn test_cat_powers_of_ten() {
+        let input = parse_input("1010: 10 10\n100100: 100 100\n").unwrap();
+        assert_eq!(part2(&input), 101110);
+        assert_eq!(part2_reference(input), 101110);
+    }
+}
@@ -0,0 +1,144 @@
+use crate::geometry::Point;
+use crate::grid::Grid;
+use crate::parse::ParseError;
+use crate::render::{self, Color, Frames};
+use crate::rng::Rng;
+use crate::solution::{Answer, Solution};
+use itertools::Itertools;
+use std::collections::{HashMap, HashSet};
+
+pub struct Day8;
+
+impl Solution for Day8 {
+    const DAY: u32 = 8;
+    type Input = Grid<char>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1(input: &Self::Input) -> Answer {
+        part1(input).into()
+    }
+
+    fn part2(input: &Self::Input) -> Answer {
+        part2(input).into()
+    }
+
+    fn generate(rng: &mut Rng, size: usize) -> Option<String> {
+        Some(generate(rng, size))
+    }
+
+    fn frames(input: &Self::Input) -> Option<Frames<'_>> {
+        Some(Box::new(std::iter::once(antinode_map(input))))
+    }
+}
+
+fn parse_input(input: &str) -> Result<Grid<char>, ParseError> {
+    Grid::parse(input, Ok)
+}
+
+fn antennas(map: &Grid<char>) -> HashMap<char, Vec<Point>> {
+    let mut res: HashMap<char, Vec<Point>> = HashMap::new();
+    for (pos, &ch) in map.iter() {
+        if ch != '.' {
+            res.entry(ch).or_default().push(pos);
+        }
+    }
+    res
+}
+
+/// Antinodes on both sides of the antennas `a1` and `a2`, at up to
+/// `max_multiplicator` times their distance.
+fn antinodes<T>(a1: Point, a2: Point, map: &Grid<T>, max_multiplicator: usize) -> Vec<Point> {
+    let mut res = Vec::new();
+    for (from, away) in [(a1, a1 - a2), (a2, a2 - a1)] {
+        for i in 1..=max_multiplicator {
+            match map.step(from, away * i as isize) {
+                Some(node) => res.push(node),
+                None => break,
+            }
+        }
+    }
+    res
+}
+
+/// All antinodes of antennas with the same frequency, resonant ones being at
+/// any multiple of the antennas' distance, including the antennas themselves.
+fn antinode_set(input: &Grid<char>, resonant: bool) -> HashSet<Point> {
+    let max_multiplicator = if resonant { usize::MAX } else { 1 };
+    let mut res = HashSet::new();
+    for antennas in antennas(input).values() {
+        for pair in antennas.iter().combinations(2) {
+            if resonant {
+                res.insert(*pair[0]);
+                res.insert(*pair[1]);
+            }
+            res.extend(antinodes(*pair[0], *pair[1], input, max_multiplicator));
+        }
+    }
+    res
+}
+
+fn part1(input: &Grid<char>) -> usize {
+    antinode_set(input, false).len()
+}
+
+fn part2(input: &Grid<char>) -> usize {
+    antinode_set(input, true).len()
+}
+
+/// Antennas colored by frequency over their antinodes, with the resonant ones
+/// of part 2 dimmer than those of part 1.
+fn antinode_map(input: &Grid<char>) -> Grid<Color> {
+    let mut colors = input.map(|_| render::DARK);
+    for node in antinode_set(input, true) {
+        colors[node] = render::blend(render::DARK, render::YELLOW, 0.4);
+    }
+    for node in antinode_set(input, false) {
+        colors[node] = render::YELLOW;
+    }
+    for (pos, &ch) in input.iter() {
+        if ch != '.' {
+            colors[pos] = render::hue(ch as u32);
+        }
+    }
+    colors
+}
+
+/// A `size` by `size` map with antennas of a few frequencies.
+fn generate(rng: &mut Rng, size: usize) -> String {
+    const FREQUENCIES: &[u8] = b"0123456789abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ";
+    let size = size.max(1);
+    let mut map = Grid::from_vec(size, size, vec!['.'; size * size]);
+    for _ in 0..size.div_ceil(3) {
+        let freq = *rng.choose(FREQUENCIES) as char;
+        for _ in 0..rng.gen_range(2..=4) {
+            map[Point::new(rng.index(size), rng.index(size))] = freq;
+        }
+    }
+    map.to_string()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::examples;
+
+    #[test]
+    fn test_same_column() {
+        let i = parse_input("..a.\n....\n..a.\n....\n").unwrap();
+        assert_eq!(part1(&i), 0);
+        assert_eq!(part2(&i), 2);
+    }
+
+    #[test]
+    fn test_part1() {
+        examples::check::<Day8>(1);
+    }
+
+    #[test]
+    fn test_part2() {
+        examples::check::<Day8>(2);
+    }
+}
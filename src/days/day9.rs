@@ -0,0 +1,376 @@
+use crate::grid::Grid;
+use crate::parse::ParseError;
+use crate::render::{self, Color, Frames};
+use crate::rng::Rng;
+use crate::solution::{Answer, Solution};
+use crate::stepper::Stepper;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum FileEntry {
+    Free,
+    File(u32),
+}
+
+#[derive(Clone, Debug)]
+pub struct Span {
+    entry: FileEntry,
+    index: usize,
+    length: usize,
+}
+
+pub struct Day9;
+
+impl Solution for Day9 {
+    const DAY: u32 = 9;
+    type Input = (Vec<FileEntry>, Vec<Span>, Vec<Span>);
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        let (files, empties) = parse_input_to_spans(input)?;
+        Ok((parse_input_to_vec(input)?, files, empties))
+    }
+
+    fn part1((blocks, _, _): &Self::Input) -> Answer {
+        part1(blocks.clone()).into()
+    }
+
+    fn part2((_, files, empties): &Self::Input) -> Answer {
+        part2(files.clone(), empties.clone()).into()
+    }
+
+    fn part1_reference((_, files, empties): &Self::Input) -> Option<Answer> {
+        Some(part1_reference(files.clone(), empties.clone()).into())
+    }
+
+    fn generate(rng: &mut Rng, size: usize) -> Option<String> {
+        Some(generate(rng, size))
+    }
+
+    fn frames((_, files, empties): &Self::Input) -> Option<Frames<'_>> {
+        Some(compaction(files, empties))
+    }
+
+    fn stepper((_, files, empties): &Self::Input) -> Option<Box<dyn Stepper>> {
+        Some(Box::new(Compaction::new(files, empties)))
+    }
+}
+
+fn digit(input: &str, pos: usize, c: char) -> Result<u32, ParseError> {
+    c.to_digit(10)
+        .ok_or_else(|| ParseError::at(input, &input[pos..pos + c.len_utf8()], "a digit"))
+}
+
+fn parse_input_to_vec(input: &str) -> Result<Vec<FileEntry>, ParseError> {
+    let mut ret = Vec::new();
+    for (i, (pos, c)) in input.char_indices().enumerate() {
+        if c == '\n' {
+            break;
+        }
+        let i: u32 = i.try_into().unwrap();
+        let num = digit(input, pos, c)?;
+        let e = if i.is_multiple_of(2) {
+            FileEntry::File(i / 2)
+        } else {
+            FileEntry::Free
+        };
+        for _ in 0..num {
+            ret.push(e);
+        }
+    }
+    Ok(ret)
+}
+
+fn parse_input_to_spans(input: &str) -> Result<(Vec<Span>, Vec<Span>), ParseError> {
+    let mut file_span = Vec::new();
+    let mut empty_span = Vec::new();
+    let mut index = 0;
+
+    for (i, (pos, c)) in input.char_indices().enumerate() {
+        if c == '\n' {
+            break;
+        }
+        let i: u32 = i.try_into().unwrap();
+        let length = digit(input, pos, c)? as usize;
+        if i.is_multiple_of(2) {
+            file_span.push(Span {
+                entry: FileEntry::File(i / 2),
+                index,
+                length,
+            })
+        } else {
+            empty_span.push(Span {
+                entry: FileEntry::Free,
+                index,
+                length,
+            })
+        }
+        index += length;
+    }
+
+    Ok((file_span, empty_span))
+}
+
+fn checksum(input: &[FileEntry]) -> usize {
+    input
+        .iter()
+        .enumerate()
+        .map(|(i, e)| match e {
+            FileEntry::File(f) => i * (*f as usize),
+            FileEntry::Free => 0,
+        })
+        .sum()
+}
+
+// TODO: rework to also work on vectos of Span and benchamrk
+fn part1(mut input: Vec<FileEntry>) -> usize {
+    fn next_free_idx(input: &[FileEntry], start: usize, max: usize) -> Option<usize> {
+        (start..max).find(|&i| input[i] == FileEntry::Free)
+    }
+    let free_idx = 0;
+    for read_idx in (0..input.len()).rev() {
+        if let Some(free_idx) = next_free_idx(&input, free_idx, read_idx) {
+            if input[read_idx] == FileEntry::Free {
+                continue;
+            }
+            input[free_idx] = input[read_idx];
+            input[read_idx] = FileEntry::Free;
+        } else {
+            break;
+        }
+    }
+
+    checksum(&input)
+}
+
+/// Part 1 on spans, moving as much of the last file as fits into the first free span.
+fn part1_reference(mut files: Vec<Span>, empties: Vec<Span>) -> usize {
+    let mut moved = Vec::new();
+    let mut empties = empties.into_iter().filter(|e| e.length > 0);
+    let mut free = empties.next();
+    while let (Some(file), Some(gap)) = (files.last_mut(), free.as_mut()) {
+        if gap.index > file.index {
+            break;
+        }
+        // The end of the file moves, its start stays where it is.
+        let length = file.length.min(gap.length);
+        moved.push(Span {
+            entry: file.entry,
+            index: gap.index,
+            length,
+        });
+        file.length -= length;
+        gap.index += length;
+        gap.length -= length;
+        if file.length == 0 {
+            files.pop();
+        }
+        if gap.length == 0 {
+            free = empties.next();
+        }
+    }
+
+    let mut sum = 0;
+    for f in files.iter().chain(&moved) {
+        if let FileEntry::File(id) = f.entry {
+            for i in 0..f.length {
+                sum += (f.index + i) * id as usize;
+            }
+        }
+    }
+    sum
+}
+
+/// Moves `file` into the leftmost free span before it that is large enough,
+/// returning whether there was one.
+fn move_file(file: &mut Span, empties: &mut [Span]) -> bool {
+    let Some(idx) = empties
+        .iter()
+        .position(|e| (e.index < file.index) && (e.length >= file.length))
+    else {
+        return false;
+    };
+    file.index = empties[idx].index;
+    empties[idx].length -= file.length;
+    empties[idx].index += file.length;
+    // TODO: should we drop empty spans with length 0? -> in that case VecDeque?
+    true
+}
+
+fn part2(mut files: Vec<Span>, mut empties: Vec<Span>) -> usize {
+    for file in files.iter_mut().rev() {
+        move_file(file, &mut empties);
+    }
+
+    let mut sum = 0;
+    for f in files {
+        if let FileEntry::File(id) = f.entry {
+            for i in 0..(f.length) {
+                sum += (f.index + i) * id as usize;
+            }
+        }
+    }
+    sum
+}
+
+/// Part 2 one file at a time, for visualisations and the stepper.
+#[derive(Clone)]
+struct Compaction {
+    files: Vec<Span>,
+    empties: Vec<Span>,
+    /// Number of files still to consider, from the right.
+    next: usize,
+    /// Number of blocks on the disk.
+    len: usize,
+}
+
+impl Compaction {
+    fn new(files: &[Span], empties: &[Span]) -> Self {
+        Self {
+            len: files
+                .iter()
+                .chain(empties)
+                .map(|s| s.index + s.length)
+                .max()
+                .unwrap_or(0),
+            next: files.len(),
+            files: files.to_vec(),
+            empties: empties.to_vec(),
+        }
+    }
+
+    /// Tries to move the next file, returning whether it moved or `None` once
+    /// every file was considered.
+    fn advance(&mut self) -> Option<bool> {
+        self.next = self.next.checked_sub(1)?;
+        Some(move_file(&mut self.files[self.next], &mut self.empties))
+    }
+
+    /// The file in every block of the disk.
+    fn blocks(&self) -> Vec<FileEntry> {
+        let mut blocks = vec![FileEntry::Free; self.len];
+        for file in &self.files {
+            blocks[file.index..file.index + file.length].fill(file.entry);
+        }
+        blocks
+    }
+
+    /// The disk as a roughly square grid read row by row, each file in its own color.
+    fn colors(&self) -> Grid<Color> {
+        let width = (self.len as f64).sqrt().ceil().max(1.0) as usize;
+        let mut colors: Vec<_> = self
+            .blocks()
+            .into_iter()
+            .map(|b| match b {
+                FileEntry::File(id) => render::hue(id),
+                FileEntry::Free => render::DARK,
+            })
+            .collect();
+        colors.resize(self.len.div_ceil(width) * width, render::DARK);
+        Grid::from_vec(width, colors.len() / width, colors)
+    }
+}
+
+impl Stepper for Compaction {
+    fn step(&mut self) -> bool {
+        self.advance().is_some()
+    }
+
+    /// The disk like in the puzzle text, showing only the last digit of file
+    /// IDs, 100 blocks per line.
+    fn render(&self) -> String {
+        let blocks: Vec<_> = self
+            .blocks()
+            .into_iter()
+            .map(|b| match b {
+                FileEntry::File(id) => char::from_digit(id % 10, 10).unwrap(),
+                FileEntry::Free => '.',
+            })
+            .collect();
+        let mut out: Vec<String> = blocks.chunks(100).map(String::from_iter).collect();
+        match self.next.checked_sub(1) {
+            Some(i) => out.push(format!("Next file: {:?}", self.files[i].entry)),
+            None => out.push(format!("Checksum: {}", checksum(&self.blocks()))),
+        }
+        out.join("\n")
+    }
+}
+
+/// The compaction of part 2, one frame per moved file.
+fn compaction(files: &[Span], empties: &[Span]) -> Frames<'static> {
+    let mut compaction = Compaction::new(files, empties);
+    let start = compaction.colors();
+    let moves = std::iter::from_fn(move || loop {
+        if compaction.advance()? {
+            return Some(compaction.colors());
+        }
+    });
+    Box::new(std::iter::once(start).chain(moves))
+}
+
+/// A disk map of `size` digits, alternating file and free space lengths.
+fn generate(rng: &mut Rng, size: usize) -> String {
+    let mut out = String::new();
+    for i in 0..size.max(1) {
+        let digit = if i % 2 == 0 {
+            rng.gen_range(1..=9)
+        } else {
+            rng.gen_range(0..=9)
+        };
+        out += &digit.to_string();
+    }
+    out.push('\n');
+    out
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::examples;
+    use crate::geometry::Point;
+
+    #[test]
+    fn test_compaction() {
+        let (_, files, empties) = Day9::parse("12345\n").unwrap();
+        let frames: Vec<_> = compaction(&files, &empties).collect();
+        // Files 2 and 1 do not fit anywhere further left.
+        assert_eq!(frames.len(), 1);
+        let (_, files, empties) = Day9::parse("2333133121414131402\n").unwrap();
+        let frames: Vec<_> = compaction(&files, &empties).collect();
+        // The start and one frame each for files 9, 7, 4 and 2.
+        assert_eq!(frames.len(), 5);
+        assert_eq!((frames[0].width(), frames[0].height()), (7, 6));
+        // File 9 moves into the gap right after file 0.
+        assert_eq!(frames[1][Point::new(2, 0)], render::hue(9));
+        assert_eq!(frames[0][Point::new(2, 0)], render::DARK);
+
+        let mut stepper = Compaction::new(&files, &empties);
+        assert!(stepper.render().ends_with("Next file: File(9)"));
+        while stepper.step() {}
+        assert_eq!(
+            stepper.render(),
+            "00992111777.44.333....5555.6666.....8888..\nChecksum: 2858"
+        );
+    }
+
+    #[test]
+    fn test_part1() {
+        examples::check::<Day9>(1);
+    }
+
+    #[test]
+    fn test_reference() {
+        let mut rng = Rng::new(9);
+        for input in ["12345\n", "2333133121414131402\n", "9\n", "10101\n"]
+            .map(String::from)
+            .into_iter()
+            .chain([1, 2, 7, 40].map(|size| generate(&mut rng, size)))
+        {
+            let (blocks, files, empties) = Day9::parse(&input).unwrap();
+            assert_eq!(part1(blocks), part1_reference(files, empties), "{input}");
+        }
+    }
+
+    #[test]
+    fn test_part2() {
+        examples::check::<Day9>(2);
+    }
+}
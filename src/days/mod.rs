@@ -0,0 +1,52 @@
+use crate::solution::DynSolution;
+
+/// Declares the day modules and collects their solutions into [`DAYS`].
+macro_rules! days {
+    ($($day:ident::$solution:ident),* $(,)?) => {
+        $(pub mod $day;)*
+
+        /// All registered days, in order.
+        pub const DAYS: &[&dyn DynSolution] = &[$(&$day::$solution),*];
+    };
+}
+
+days!(
+    day1::Day1,
+    day2::Day2,
+    day3::Day3,
+    day4::Day4,
+    day5::Day5,
+    day6::Day6,
+    day7::Day7,
+    day8::Day8,
+    day9::Day9,
+    day10::Day10,
+    day11::Day11,
+);
+
+pub fn get(day: u32) -> Option<&'static dyn DynSolution> {
+    DAYS.iter().find(|d| d.day() == day).copied()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::rng::Rng;
+
+    #[test]
+    fn test_generated_inputs() {
+        for day in DAYS {
+            for seed in 0..5 {
+                let mut rng = Rng::new(seed);
+                let input = day.generate(&mut rng, 12).expect("Generator");
+                let again = day.generate(&mut Rng::new(seed), 12);
+                assert_eq!(Some(&input), again.as_ref(), "day {}", day.day());
+                let parsed = day
+                    .parse(&input)
+                    .unwrap_or_else(|e| panic!("{}\n{input}", e.diagnostic()));
+                parsed.part1();
+                parsed.part2();
+            }
+        }
+    }
+}
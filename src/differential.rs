@@ -0,0 +1,206 @@
+use crate::parallel;
+use crate::rng::Rng;
+use crate::solution::{Answer, DynSolution, Parsed};
+use std::fmt;
+use std::ops::Range;
+
+/// Result of running one implementation, with panics turned into their message.
+pub type Outcome = Result<Answer, String>;
+
+/// A generated input on which the optimised and the reference implementation disagree.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Mismatch {
+    pub day: u32,
+    pub part: u8,
+    /// Seed the original input was generated with.
+    pub seed: u64,
+    /// The smallest input found that still shows the disagreement.
+    pub input: String,
+    pub answer: Outcome,
+    pub reference: Outcome,
+}
+
+impl fmt::Display for Mismatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let show = |o: &Outcome| match o {
+            Ok(a) => a.to_string(),
+            Err(msg) => format!("panic ({msg})"),
+        };
+        writeln!(
+            f,
+            "Day {} part {} (seed {}): answer {}, reference {}",
+            self.day,
+            self.part,
+            self.seed,
+            show(&self.answer),
+            show(&self.reference)
+        )?;
+        write!(f, "{}", self.input)
+    }
+}
+
+/// Summary of a differential run over a range of seeds.
+#[derive(Clone, Debug, Default)]
+pub struct Report {
+    /// Number of generated inputs both implementations were compared on.
+    pub checked: usize,
+    pub mismatch: Option<Mismatch>,
+}
+
+fn catch(f: impl FnOnce() -> Option<Answer>) -> Option<Outcome> {
+    parallel::catch(f).transpose()
+}
+
+fn solve(parsed: &dyn Parsed, part: u8) -> Option<(Outcome, Outcome)> {
+    let reference = match part {
+        1 => catch(|| parsed.part1_reference())?,
+        _ => catch(|| parsed.part2_reference())?,
+    };
+    let answer = match part {
+        1 => catch(|| Some(parsed.part1()))?,
+        _ => catch(|| Some(parsed.part2()))?,
+    };
+    Some((answer, reference))
+}
+
+/// Runs both implementations of `part` on `input`, returning their outcomes if they differ.
+///
+/// Inputs that do not parse and days without a reference never disagree.
+pub fn disagreement(day: &dyn DynSolution, part: u8, input: &str) -> Option<(Outcome, Outcome)> {
+    let parsed = day.parse(input).ok()?;
+    solve(parsed.as_ref(), part).filter(|(a, r)| a != r)
+}
+
+/// Shrinks `input` while the implementations keep disagreeing, first dropping
+/// whole lines and then single tokens.
+pub fn minimize(day: &dyn DynSolution, part: u8, input: &str) -> String {
+    let mut input = input.to_string();
+    for separators in [&['\n'][..], &['\n', ' ', ','][..]] {
+        loop {
+            let pieces: Vec<&str> = input.split_inclusive(separators).collect();
+            let smaller = (0..pieces.len()).find_map(|skip| {
+                let candidate: String = pieces
+                    .iter()
+                    .enumerate()
+                    .filter(|&(i, _)| i != skip)
+                    .map(|(_, p)| *p)
+                    .collect();
+                disagreement(day, part, &candidate).map(|_| candidate)
+            });
+            match smaller {
+                Some(smaller) => input = smaller,
+                None => break,
+            }
+        }
+    }
+    input
+}
+
+/// Compares both parts of `day` with their references on inputs generated from
+/// `seeds`, stopping at the first disagreement.
+pub fn check(day: &dyn DynSolution, seeds: Range<u64>, size: usize) -> Report {
+    let mut report = Report::default();
+    for seed in seeds {
+        let Some(input) = day.generate(&mut Rng::new(seed), size) else {
+            break;
+        };
+        let Ok(parsed) = day.parse(&input) else {
+            continue;
+        };
+        for part in [1, 2] {
+            let Some((answer, reference)) = solve(parsed.as_ref(), part) else {
+                continue;
+            };
+            report.checked += 1;
+            if answer != reference {
+                let input = minimize(day, part, &input);
+                let (answer, reference) =
+                    disagreement(day, part, &input).unwrap_or((answer, reference));
+                report.mismatch = Some(Mismatch {
+                    day: day.day(),
+                    part,
+                    seed,
+                    input,
+                    answer,
+                    reference,
+                });
+                return report;
+            }
+        }
+    }
+    report
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::days;
+    use crate::parse::ParseError;
+    use crate::solution::Solution;
+
+    /// Sums numbers, with a "fast" part 1 that forgets anything above 100.
+    struct Buggy;
+
+    impl Solution for Buggy {
+        const DAY: u32 = 0;
+        type Input = Vec<u64>;
+
+        fn parse(input: &str) -> Result<Self::Input, ParseError> {
+            input
+                .split_whitespace()
+                .map(|n| n.parse().map_err(|_| ParseError::at(input, n, "a number")))
+                .collect()
+        }
+
+        fn part1(input: &Self::Input) -> Answer {
+            input.iter().filter(|&&n| n <= 100).sum::<u64>().into()
+        }
+
+        fn part2(input: &Self::Input) -> Answer {
+            input.len().into()
+        }
+
+        fn part1_reference(input: &Self::Input) -> Option<Answer> {
+            Some(input.iter().sum::<u64>().into())
+        }
+
+        fn generate(rng: &mut Rng, size: usize) -> Option<String> {
+            let lines: Vec<_> = (0..size)
+                .map(|_| format!("{} {}\n", rng.below(60), rng.below(60)))
+                .collect();
+            let mut out = lines.concat();
+            if rng.chance(0.3) {
+                out += "7 150 3\n";
+            }
+            Some(out)
+        }
+    }
+
+    #[test]
+    fn test_minimize() {
+        let input = "1 2\n3 150\n4 5 6\n";
+        assert_eq!(minimize(&Buggy, 1, input), "150\n");
+        assert_eq!(disagreement(&Buggy, 1, "1 2\n"), None);
+        assert_eq!(disagreement(&Buggy, 1, "x\n"), None);
+        assert_eq!(disagreement(&Buggy, 2, "150\n"), None);
+    }
+
+    #[test]
+    fn test_check() {
+        let report = check(&Buggy, 0..100, 5);
+        let mismatch = report.mismatch.unwrap();
+        assert_eq!(mismatch.part, 1);
+        assert_eq!(mismatch.input, "150 ");
+        assert_eq!(mismatch.answer, Ok(Answer::Int(0)));
+        assert_eq!(mismatch.reference, Ok(Answer::Int(150)));
+        assert_eq!(report.checked, mismatch.seed as usize + 1);
+    }
+
+    #[test]
+    fn test_days_agree() {
+        for day in days::DAYS {
+            let report = check(*day, 0..10, 12);
+            assert_eq!(report.mismatch, None);
+        }
+    }
+}
@@ -0,0 +1,116 @@
+use crate::ledger::Answers;
+use crate::solution::Solution;
+use std::collections::BTreeMap;
+use std::io;
+use std::path::PathBuf;
+
+/// Day, file name and contents of every file below `examples/`, embedded at build time.
+static FILES: &[(u32, &str, &str)] = include!(concat!(env!("OUT_DIR"), "/examples.rs"));
+
+/// A puzzle example from `examples/dayN`, with the answers given in the puzzle text.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Example {
+    pub name: String,
+    pub input: String,
+    pub expected: Answers,
+}
+
+/// Directory of the examples of `day`, relative to the repository root.
+pub fn dir(day: u32) -> PathBuf {
+    PathBuf::from(format!("examples/day{day}"))
+}
+
+/// The embedded file `name` from the examples of `day`.
+///
+/// Panics if there is no such file, for use in unit tests.
+pub fn input(day: u32, name: &str) -> &'static str {
+    FILES
+        .iter()
+        .find(|&&(d, n, _)| d == day && n == name)
+        .map(|&(_, _, contents)| contents)
+        .unwrap_or_else(|| panic!("No example {name} for day {day}"))
+}
+
+/// Loads the examples of `day`, sorted by name.
+///
+/// Each `*.txt` file in the day's directory is an example input, and
+/// `expected.toml` holds the known answers keyed by file name. The files are
+/// embedded when building, so this works from any working directory.
+pub fn load(day: u32) -> io::Result<Vec<Example>> {
+    let files = FILES.iter().filter(|&&(d, _, _)| d == day);
+    let expected: BTreeMap<String, Answers> =
+        match files.clone().find(|&&(_, name, _)| name == "expected.toml") {
+            Some(&(_, _, f)) => {
+                toml::from_str(f).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?
+            }
+            None => BTreeMap::new(),
+        };
+    // The files are sorted by day and name.
+    Ok(files
+        .filter(|&&(_, name, _)| name.ends_with(".txt"))
+        .map(|&(_, name, input)| Example {
+            name: name.to_string(),
+            input: input.to_string(),
+            expected: expected.get(name).cloned().unwrap_or_default(),
+        })
+        .collect())
+}
+
+/// Runs `part` of `S` on every example of its day that has an expected answer for it.
+///
+/// Panics on the first mismatch or if no example covers `part`, for use in unit tests.
+pub fn check<S: Solution>(part: u8) {
+    let examples = load(S::DAY).expect("Cannot load examples");
+    let mut checked = 0;
+    for ex in examples {
+        let Some(expected) = ex.expected.part(part) else {
+            continue;
+        };
+        let input = S::parse(&ex.input).unwrap_or_else(|e| panic!("{}: {e}", ex.name));
+        let answer = match part {
+            1 => S::part1(&input),
+            _ => S::part2(&input),
+        };
+        assert_eq!(
+            answer.to_string(),
+            expected,
+            "day {} part {part} on {}",
+            S::DAY,
+            ex.name
+        );
+        checked += 1;
+    }
+    assert!(
+        checked > 0,
+        "No example of day {} covers part {part}",
+        S::DAY
+    );
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_load() {
+        let ex = load(3).unwrap();
+        let names: Vec<_> = ex.iter().map(|e| e.name.as_str()).collect();
+        assert_eq!(names, vec!["part1.txt", "part2.txt"]);
+        assert_eq!(ex[0].expected.part(1), Some("161"));
+        assert_eq!(ex[0].expected.part(2), None);
+        assert!(ex[1].input.starts_with("xmul(2,4)&mul[3,7]"));
+    }
+
+    #[test]
+    fn test_input() {
+        assert_eq!(input(1, "example.txt"), load(1).unwrap()[0].input);
+        assert!(load(99).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_every_day_has_examples() {
+        for day in crate::days::DAYS {
+            assert!(!load(day.day()).unwrap().is_empty(), "day {}", day.day());
+        }
+    }
+}
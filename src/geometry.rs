@@ -0,0 +1,277 @@
+use std::fmt;
+use std::ops::{Add, Mul, Neg, Sub};
+
+/// A signed offset, with `y` growing downwards like grid rows.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Vec2 {
+    pub x: isize,
+    pub y: isize,
+}
+
+impl Vec2 {
+    pub const ZERO: Vec2 = Vec2::new(0, 0);
+
+    pub const fn new(x: isize, y: isize) -> Self {
+        Self { x, y }
+    }
+}
+
+impl Add for Vec2 {
+    type Output = Vec2;
+
+    fn add(self, other: Vec2) -> Vec2 {
+        Vec2::new(self.x + other.x, self.y + other.y)
+    }
+}
+
+impl Sub for Vec2 {
+    type Output = Vec2;
+
+    fn sub(self, other: Vec2) -> Vec2 {
+        Vec2::new(self.x - other.x, self.y - other.y)
+    }
+}
+
+impl Neg for Vec2 {
+    type Output = Vec2;
+
+    fn neg(self) -> Vec2 {
+        Vec2::new(-self.x, -self.y)
+    }
+}
+
+impl Mul<isize> for Vec2 {
+    type Output = Vec2;
+
+    fn mul(self, n: isize) -> Vec2 {
+        Vec2::new(self.x * n, self.y * n)
+    }
+}
+
+impl From<(isize, isize)> for Vec2 {
+    fn from((x, y): (isize, isize)) -> Self {
+        Vec2::new(x, y)
+    }
+}
+
+impl From<Vec2> for (isize, isize) {
+    fn from(v: Vec2) -> Self {
+        (v.x, v.y)
+    }
+}
+
+/// A position on a grid, with the origin in the top left corner.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Point {
+    pub x: usize,
+    pub y: usize,
+}
+
+impl Point {
+    pub const fn new(x: usize, y: usize) -> Self {
+        Self { x, y }
+    }
+
+    /// Moves by `v`, returning `None` if either coordinate would become negative.
+    ///
+    /// Use [`Grid::step`](crate::grid::Grid::step) to also stay within a grid.
+    pub fn checked_add(self, v: Vec2) -> Option<Point> {
+        Some(Point::new(
+            self.x.checked_add_signed(v.x)?,
+            self.y.checked_add_signed(v.y)?,
+        ))
+    }
+}
+
+/// The offset leading from `other` to `self`.
+impl Sub for Point {
+    type Output = Vec2;
+
+    fn sub(self, other: Point) -> Vec2 {
+        Vec2::new(
+            self.x as isize - other.x as isize,
+            self.y as isize - other.y as isize,
+        )
+    }
+}
+
+impl From<(usize, usize)> for Point {
+    fn from((x, y): (usize, usize)) -> Self {
+        Point::new(x, y)
+    }
+}
+
+impl From<Point> for (usize, usize) {
+    fn from(p: Point) -> Self {
+        (p.x, p.y)
+    }
+}
+
+impl fmt::Display for Point {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "({}, {})", self.x, self.y)
+    }
+}
+
+/// One of the four horizontal and vertical directions.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Direction4 {
+    Up,
+    Right,
+    Down,
+    Left,
+}
+
+impl Direction4 {
+    /// All directions, clockwise starting at `Up`.
+    pub const ALL: [Direction4; 4] = [
+        Direction4::Up,
+        Direction4::Right,
+        Direction4::Down,
+        Direction4::Left,
+    ];
+
+    fn index(self) -> usize {
+        self as usize
+    }
+
+    /// Rotated by 90 degrees clockwise.
+    pub fn turn_right(self) -> Self {
+        Self::ALL[(self.index() + 1) % 4]
+    }
+
+    /// Rotated by 90 degrees counterclockwise.
+    pub fn turn_left(self) -> Self {
+        Self::ALL[(self.index() + 3) % 4]
+    }
+
+    pub fn opposite(self) -> Self {
+        Self::ALL[(self.index() + 2) % 4]
+    }
+
+    /// A step of length one in this direction.
+    pub fn vec(self) -> Vec2 {
+        match self {
+            Direction4::Up => Vec2::new(0, -1),
+            Direction4::Right => Vec2::new(1, 0),
+            Direction4::Down => Vec2::new(0, 1),
+            Direction4::Left => Vec2::new(-1, 0),
+        }
+    }
+}
+
+/// One of the eight horizontal, vertical and diagonal directions.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Direction8 {
+    Up,
+    UpRight,
+    Right,
+    DownRight,
+    Down,
+    DownLeft,
+    Left,
+    UpLeft,
+}
+
+impl Direction8 {
+    /// All directions, clockwise starting at `Up`.
+    pub const ALL: [Direction8; 8] = [
+        Direction8::Up,
+        Direction8::UpRight,
+        Direction8::Right,
+        Direction8::DownRight,
+        Direction8::Down,
+        Direction8::DownLeft,
+        Direction8::Left,
+        Direction8::UpLeft,
+    ];
+
+    fn index(self) -> usize {
+        self as usize
+    }
+
+    /// Rotated by 45 degrees clockwise.
+    pub fn turn_right(self) -> Self {
+        Self::ALL[(self.index() + 1) % 8]
+    }
+
+    /// Rotated by 45 degrees counterclockwise.
+    pub fn turn_left(self) -> Self {
+        Self::ALL[(self.index() + 7) % 8]
+    }
+
+    pub fn opposite(self) -> Self {
+        Self::ALL[(self.index() + 4) % 8]
+    }
+
+    /// A step in this direction, diagonal ones moving along both axes.
+    pub fn vec(self) -> Vec2 {
+        match self {
+            Direction8::Up => Vec2::new(0, -1),
+            Direction8::UpRight => Vec2::new(1, -1),
+            Direction8::Right => Vec2::new(1, 0),
+            Direction8::DownRight => Vec2::new(1, 1),
+            Direction8::Down => Vec2::new(0, 1),
+            Direction8::DownLeft => Vec2::new(-1, 1),
+            Direction8::Left => Vec2::new(-1, 0),
+            Direction8::UpLeft => Vec2::new(-1, -1),
+        }
+    }
+}
+
+impl From<Direction4> for Direction8 {
+    fn from(d: Direction4) -> Self {
+        Direction8::ALL[d.index() * 2]
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_vec2() {
+        let v = Vec2::new(2, -3);
+        assert_eq!(v + Vec2::new(1, 1), Vec2::new(3, -2));
+        assert_eq!(v - v, Vec2::ZERO);
+        assert_eq!(-v, Vec2::new(-2, 3));
+        assert_eq!(v * 3, Vec2::new(6, -9));
+    }
+
+    #[test]
+    fn test_point() {
+        let p = Point::new(2, 3);
+        assert_eq!(p.checked_add(Vec2::new(-2, 1)), Some(Point::new(0, 4)));
+        assert_eq!(p.checked_add(Vec2::new(0, -4)), None);
+        assert_eq!(Point::new(5, 1) - p, Vec2::new(3, -2));
+        assert_eq!(p.checked_add(Point::new(5, 1) - p), Some(Point::new(5, 1)));
+        assert_eq!(Point::from((2, 3)), p);
+        assert_eq!(p.to_string(), "(2, 3)");
+    }
+
+    #[test]
+    fn test_direction4() {
+        for d in Direction4::ALL {
+            assert_eq!(d.turn_right().turn_left(), d);
+            assert_eq!(d.turn_right().turn_right(), d.opposite());
+            assert_eq!(d.opposite().vec(), -d.vec());
+            assert_eq!(Direction8::from(d).vec(), d.vec());
+        }
+        assert_eq!(Direction4::Up.turn_right(), Direction4::Right);
+        assert_eq!(Direction4::Up.turn_left(), Direction4::Left);
+    }
+
+    #[test]
+    fn test_direction8() {
+        for d in Direction8::ALL {
+            assert_eq!(d.turn_right().turn_left(), d);
+            assert_eq!(
+                d.turn_right().turn_right().turn_right().turn_right(),
+                d.opposite()
+            );
+            assert_eq!(d.opposite().vec(), -d.vec());
+        }
+        assert_eq!(Direction8::Left.turn_right(), Direction8::UpLeft);
+        assert_eq!(Direction8::Up.turn_left(), Direction8::UpLeft);
+    }
+}
@@ -0,0 +1,345 @@
+use crate::geometry::{Point, Vec2};
+use crate::parse::{self, ParseError};
+use std::fmt;
+use std::ops::{Index, IndexMut};
+
+/// Offsets of the 4-neighborhood: left, up, right, down.
+const OFFSETS4: [Vec2; 4] = [
+    Vec2::new(-1, 0),
+    Vec2::new(0, -1),
+    Vec2::new(1, 0),
+    Vec2::new(0, 1),
+];
+
+/// Offsets of the 8-neighborhood, row by row.
+const OFFSETS8: [Vec2; 8] = [
+    Vec2::new(-1, -1),
+    Vec2::new(0, -1),
+    Vec2::new(1, -1),
+    Vec2::new(-1, 0),
+    Vec2::new(1, 0),
+    Vec2::new(-1, 1),
+    Vec2::new(0, 1),
+    Vec2::new(1, 1),
+];
+
+/// A rectangular, row-major grid addressed by [`Point`]s with the origin in the
+/// top left corner.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    /// Creates a grid from row-major `cells`.
+    ///
+    /// Panics if `cells.len() != width * height`.
+    pub fn from_vec(width: usize, height: usize, cells: Vec<T>) -> Self {
+        assert_eq!(cells.len(), width * height, "Invalid grid size");
+        Self {
+            width,
+            height,
+            cells,
+        }
+    }
+
+    /// Creates a grid from a list of rows.
+    ///
+    /// Panics if the rows are not all of the same length.
+    pub fn from_rows(rows: Vec<Vec<T>>) -> Self {
+        let height = rows.len();
+        let width = rows.first().map_or(0, |r| r.len());
+        let mut cells = Vec::with_capacity(width * height);
+        for (y, row) in rows.into_iter().enumerate() {
+            assert_eq!(row.len(), width, "Row {y} has a different length");
+            cells.extend(row);
+        }
+        Self::from_vec(width, height, cells)
+    }
+
+    /// Parses a grid from text, one row per line, converting each character with `f`.
+    ///
+    /// `f` returns a description of the expected characters for invalid ones.
+    pub fn parse(
+        input: &str,
+        mut f: impl FnMut(char) -> Result<T, &'static str>,
+    ) -> Result<Self, ParseError> {
+        let mut width = None;
+        let mut cells = Vec::new();
+        let mut height = 0;
+        for line in parse::lines(input) {
+            for (i, c) in line.text.char_indices() {
+                let cell = f(c).map_err(|e| line.error(&line.text[i..i + c.len_utf8()], e))?;
+                cells.push(cell);
+            }
+            let len = line.text.chars().count();
+            match width {
+                None => width = Some(len),
+                Some(w) if w == len => {}
+                Some(w) => {
+                    let end = line
+                        .text
+                        .char_indices()
+                        .nth(w)
+                        .map_or(line.text.len(), |(i, _)| i);
+                    return Err(line.error(&line.text[end..], format!("a row of length {w}")));
+                }
+            }
+            height += 1;
+        }
+        Ok(Self::from_vec(width.unwrap_or(0), height, cells))
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn in_bounds(&self, pos: Point) -> bool {
+        pos.x < self.width && pos.y < self.height
+    }
+
+    pub fn get(&self, pos: Point) -> Option<&T> {
+        if self.in_bounds(pos) {
+            Some(&self.cells[pos.y * self.width + pos.x])
+        } else {
+            None
+        }
+    }
+
+    pub fn get_mut(&mut self, pos: Point) -> Option<&mut T> {
+        if self.in_bounds(pos) {
+            Some(&mut self.cells[pos.y * self.width + pos.x])
+        } else {
+            None
+        }
+    }
+
+    /// Moves `pos` by `v`, returning `None` if the result leaves the grid.
+    pub fn step(&self, pos: Point, v: Vec2) -> Option<Point> {
+        pos.checked_add(v).filter(|&p| self.in_bounds(p))
+    }
+
+    /// All positions in row-major order.
+    pub fn positions(&self) -> impl Iterator<Item = Point> {
+        let width = self.width;
+        (0..self.cells.len()).map(move |i| Point::new(i % width, i / width))
+    }
+
+    /// All cells with their positions in row-major order.
+    pub fn iter(&self) -> impl Iterator<Item = (Point, &T)> {
+        self.positions().zip(self.cells.iter())
+    }
+
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = (Point, &mut T)> {
+        let width = self.width;
+        self.cells
+            .iter_mut()
+            .enumerate()
+            .map(move |(i, c)| (Point::new(i % width, i / width), c))
+    }
+
+    /// Position of the first cell matching `pred` in row-major order.
+    pub fn find(&self, mut pred: impl FnMut(&T) -> bool) -> Option<Point> {
+        self.iter().find(|(_, c)| pred(c)).map(|(p, _)| p)
+    }
+
+    /// Horizontal and vertical neighbors of `pos` that lie within the grid.
+    pub fn neighbors4(&self, pos: Point) -> impl Iterator<Item = Point> + '_ {
+        OFFSETS4.iter().filter_map(move |&v| self.step(pos, v))
+    }
+
+    /// Horizontal, vertical and diagonal neighbors of `pos` that lie within the grid.
+    pub fn neighbors8(&self, pos: Point) -> impl Iterator<Item = Point> + '_ {
+        OFFSETS8.iter().filter_map(move |&v| self.step(pos, v))
+    }
+
+    /// Positions starting at `pos` and repeatedly moving by `v` until leaving the grid.
+    pub fn ray(&self, pos: Point, v: Vec2) -> impl Iterator<Item = Point> + '_ {
+        std::iter::successors(self.in_bounds(pos).then_some(pos), move |&p| {
+            self.step(p, v)
+        })
+    }
+
+    pub fn row(&self, y: usize) -> &[T] {
+        &self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        // `max(1)` keeps `chunks` happy for grids without columns.
+        self.cells.chunks(self.width.max(1))
+    }
+
+    /// Cells of column `x` from top to bottom.
+    ///
+    /// Panics if `x` is out of bounds, like [`Grid::row`].
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> {
+        assert!(x < self.width, "Invalid column {x}");
+        self.cells.iter().skip(x).step_by(self.width)
+    }
+
+    /// Cells on the diagonal going down and to the right from `pos`.
+    pub fn diagonal(&self, pos: Point) -> impl Iterator<Item = &T> {
+        self.ray(pos, Vec2::new(1, 1)).map(|p| &self[p])
+    }
+
+    /// Cells on the anti-diagonal going down and to the left from `pos`.
+    pub fn anti_diagonal(&self, pos: Point) -> impl Iterator<Item = &T> {
+        self.ray(pos, Vec2::new(-1, 1)).map(|p| &self[p])
+    }
+
+    /// Converts every cell with `f`, keeping the layout.
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.iter().map(f).collect(),
+        }
+    }
+}
+
+impl<T> Index<Point> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, pos: Point) -> &T {
+        self.get(pos)
+            .unwrap_or_else(|| panic!("Invalid position {pos}"))
+    }
+}
+
+impl<T> IndexMut<Point> for Grid<T> {
+    fn index_mut(&mut self, pos: Point) -> &mut T {
+        self.get_mut(pos)
+            .unwrap_or_else(|| panic!("Invalid position {pos}"))
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for row in self.rows() {
+            for c in row {
+                write!(f, "{c}")?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn p(x: usize, y: usize) -> Point {
+        Point::new(x, y)
+    }
+
+    static INP: &str = "abc
+def
+";
+
+    #[test]
+    fn test_parse() {
+        let g = Grid::parse(INP, Ok).unwrap();
+        assert_eq!(g.width(), 3);
+        assert_eq!(g.height(), 2);
+        assert_eq!(g[p(0, 0)], 'a');
+        assert_eq!(g[p(2, 1)], 'f');
+        assert_eq!(g.get(p(3, 0)), None);
+        assert_eq!(g.get(p(0, 2)), None);
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let err = Grid::parse("ab\nc\n", Ok).unwrap_err();
+        assert_eq!((err.line, err.column), (2, 2));
+        assert_eq!(err.expected, "a row of length 2");
+        let err = Grid::parse("ab\nabc\n", Ok).unwrap_err();
+        assert_eq!((err.line, err.column, err.text.as_str()), (2, 3, "c"));
+        let err = Grid::parse("12\n3x\n", |c| c.to_digit(10).ok_or("a digit")).unwrap_err();
+        assert_eq!((err.line, err.column, err.text.as_str()), (2, 2, "x"));
+    }
+
+    #[test]
+    fn test_index_mut() {
+        let mut g = Grid::from_vec(2, 2, vec![0; 4]);
+        g[p(1, 0)] = 5;
+        *g.get_mut(p(0, 1)).unwrap() = 7;
+        assert_eq!(g, Grid::from_rows(vec![vec![0, 5], vec![7, 0]]));
+        assert_eq!(g.get_mut(p(2, 0)), None);
+    }
+
+    #[test]
+    fn test_iter() {
+        let g = Grid::parse(INP, Ok).unwrap();
+        let cells: Vec<_> = g.iter().collect();
+        assert_eq!(cells[0], (p(0, 0), &'a'));
+        assert_eq!(cells[4], (p(1, 1), &'e'));
+        assert_eq!(g.find(|&c| c == 'e'), Some(p(1, 1)));
+        assert_eq!(g.find(|&c| c == 'x'), None);
+    }
+
+    #[test]
+    fn test_neighbors() {
+        let g = Grid::from_vec(3, 3, (0..9).collect());
+        let n: Vec<_> = g.neighbors4(p(0, 0)).collect();
+        assert_eq!(n, vec![p(1, 0), p(0, 1)]);
+        assert_eq!(g.neighbors4(p(1, 1)).count(), 4);
+        assert_eq!(g.neighbors8(p(0, 0)).count(), 3);
+        assert_eq!(g.neighbors8(p(1, 1)).count(), 8);
+        assert_eq!(g.neighbors8(p(2, 1)).count(), 5);
+    }
+
+    #[test]
+    fn test_step() {
+        let mut g = Grid::from_vec(3, 2, (0..6).collect());
+        let pos = p(1, 1);
+        assert_eq!(g.step(pos, Vec2::new(1, -1)), Some(p(2, 0)));
+        assert_eq!(g.step(pos, Vec2::new(2, 0)), None);
+        assert_eq!(g.step(pos, Vec2::new(0, 1)), None);
+        assert_eq!(g.step(pos, Vec2::new(-2, 0)), None);
+        assert_eq!(g[pos], 4);
+        g[pos] = 7;
+        assert_eq!(g[p(1, 1)], 7);
+    }
+
+    #[test]
+    fn test_views() {
+        let g = Grid::from_vec(3, 3, (0..9).collect());
+        assert_eq!(g.row(1), &[3, 4, 5]);
+        assert_eq!(g.rows().count(), 3);
+        assert_eq!(g.column(2).copied().collect::<Vec<_>>(), vec![2, 5, 8]);
+        assert_eq!(
+            g.diagonal(p(0, 0)).copied().collect::<Vec<_>>(),
+            vec![0, 4, 8]
+        );
+        assert_eq!(
+            g.anti_diagonal(p(2, 0)).copied().collect::<Vec<_>>(),
+            vec![2, 4, 6]
+        );
+        assert_eq!(
+            g.ray(p(1, 2), Vec2::new(0, -1)).collect::<Vec<_>>(),
+            vec![p(1, 2), p(1, 1), p(1, 0)]
+        );
+        assert_eq!(g.ray(p(3, 0), Vec2::new(1, 0)).count(), 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "Invalid column 3")]
+    fn test_column_out_of_bounds() {
+        let g = Grid::from_vec(3, 3, (0..9).collect());
+        let _ = g.column(3);
+    }
+
+    #[test]
+    fn test_display() {
+        let g = Grid::parse(INP, Ok).unwrap();
+        assert_eq!(g.to_string(), INP);
+        assert_eq!(g.map(|c| c.to_ascii_uppercase()).to_string(), "ABC\nDEF\n");
+    }
+}
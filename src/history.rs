@@ -0,0 +1,268 @@
+use crate::client::Verdict;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+/// Default location of the submission history, relative to the repository root.
+pub const DEFAULT_PATH: &str = "submissions.toml";
+
+/// How long to hold back after being rate limited without being told for how long.
+const DEFAULT_WAIT: u64 = 60;
+
+/// Submitted answers and what the site said about them, keyed by day and part.
+///
+/// ```toml
+/// not_before = 1733040000
+///
+/// [day7.part1]
+/// low = 100
+/// wrong = ["100"]
+///
+/// [[day7.part1.attempts]]
+/// answer = "100"
+/// verdict = "too low"
+/// time = 1733039000
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct History {
+    /// Unix time before which the site will not take another answer.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    not_before: Option<u64>,
+    #[serde(flatten)]
+    days: BTreeMap<String, BTreeMap<String, PartHistory>>,
+}
+
+/// Everything known about the answer of one part.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PartHistory {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub correct: Option<String>,
+    /// Largest answer known to be too low.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub low: Option<i64>,
+    /// Smallest answer known to be too high.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub high: Option<i64>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub wrong: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub attempts: Vec<Attempt>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Attempt {
+    pub answer: String,
+    pub verdict: String,
+    /// Unix time of the submission.
+    pub time: u64,
+}
+
+/// Why an answer is not worth submitting.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Refusal {
+    Solved(String),
+    KnownWrong,
+    NotAbove(i64),
+    NotBelow(i64),
+    /// Seconds left until the site takes answers again.
+    RateLimited(u64),
+}
+
+impl fmt::Display for Refusal {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Refusal::Solved(answer) => write!(f, "already solved with {answer}"),
+            Refusal::KnownWrong => write!(f, "already known to be wrong"),
+            Refusal::NotAbove(low) => write!(f, "{low} is already too low"),
+            Refusal::NotBelow(high) => write!(f, "{high} is already too high"),
+            Refusal::RateLimited(secs) => write!(f, "rate limited for another {secs}s"),
+        }
+    }
+}
+
+fn day_key(day: u32) -> String {
+    format!("day{day}")
+}
+
+fn part_key(part: u8) -> String {
+    format!("part{part}")
+}
+
+impl History {
+    pub fn parse(input: &str) -> Result<Self, toml::de::Error> {
+        toml::from_str(input)
+    }
+
+    /// Reads the history at `path`, returning an empty history if the file does not exist.
+    pub fn load(path: impl AsRef<Path>) -> io::Result<Self> {
+        match fs::read_to_string(path) {
+            Ok(f) => Self::parse(&f).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e)),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(e),
+        }
+    }
+
+    pub fn save(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        let out =
+            toml::to_string(self).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        fs::write(path, out)
+    }
+
+    pub fn part(&self, day: u32, part: u8) -> Option<&PartHistory> {
+        self.days.get(&day_key(day))?.get(&part_key(part))
+    }
+
+    /// Checks whether `answer` could still be right and may be submitted at unix time `now`.
+    pub fn check(&self, day: u32, part: u8, answer: &str, now: u64) -> Result<(), Refusal> {
+        if let Some(not_before) = self.not_before.filter(|&t| t > now) {
+            return Err(Refusal::RateLimited(not_before - now));
+        }
+        let Some(history) = self.part(day, part) else {
+            return Ok(());
+        };
+        if let Some(correct) = &history.correct {
+            return Err(Refusal::Solved(correct.clone()));
+        }
+        if history.wrong.iter().any(|w| w == answer) {
+            return Err(Refusal::KnownWrong);
+        }
+        if let Ok(n) = answer.parse::<i64>() {
+            if let Some(low) = history.low.filter(|&low| n <= low) {
+                return Err(Refusal::NotAbove(low));
+            }
+            if let Some(high) = history.high.filter(|&high| n >= high) {
+                return Err(Refusal::NotBelow(high));
+            }
+        }
+        Ok(())
+    }
+
+    /// Records the `verdict` on `answer`, submitted at unix time `now`.
+    pub fn record(&mut self, day: u32, part: u8, answer: &str, verdict: Verdict, now: u64) {
+        let history = self
+            .days
+            .entry(day_key(day))
+            .or_default()
+            .entry(part_key(part))
+            .or_default();
+        history.attempts.push(Attempt {
+            answer: answer.to_string(),
+            verdict: verdict.to_string(),
+            time: now,
+        });
+        let number = answer.parse::<i64>().ok();
+        match verdict {
+            Verdict::Correct => history.correct = Some(answer.to_string()),
+            Verdict::TooHigh | Verdict::TooLow | Verdict::Wrong => {
+                history.wrong.push(answer.to_string());
+                match (verdict, number) {
+                    (Verdict::TooHigh, Some(n)) => {
+                        history.high = Some(history.high.map_or(n, |h| h.min(n)));
+                    }
+                    (Verdict::TooLow, Some(n)) => {
+                        history.low = Some(history.low.map_or(n, |l| l.max(n)));
+                    }
+                    _ => {}
+                }
+            }
+            Verdict::RateLimited(wait) => {
+                self.not_before = Some(now + wait.map_or(DEFAULT_WAIT, |w| w.as_secs()));
+            }
+            Verdict::WrongLevel => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::client::mock::MockServer;
+    use crate::client::{Client, Config, Ureq};
+    use std::time::Duration;
+
+    #[test]
+    fn test_bounds() {
+        let mut h = History::default();
+        assert_eq!(h.check(7, 1, "200", 0), Ok(()));
+        h.record(7, 1, "100", Verdict::TooLow, 0);
+        h.record(7, 1, "300", Verdict::TooHigh, 0);
+        h.record(7, 1, "500", Verdict::TooHigh, 0);
+        h.record(7, 1, "250", Verdict::Wrong, 0);
+        assert_eq!(h.check(7, 1, "100", 0), Err(Refusal::KnownWrong));
+        assert_eq!(h.check(7, 1, "50", 0), Err(Refusal::NotAbove(100)));
+        assert_eq!(h.check(7, 1, "400", 0), Err(Refusal::NotBelow(300)));
+        assert_eq!(h.check(7, 1, "250", 0), Err(Refusal::KnownWrong));
+        assert_eq!(h.check(7, 1, "200", 0), Ok(()));
+        assert_eq!(h.check(7, 2, "50", 0), Ok(()));
+
+        h.record(7, 1, "200", Verdict::Correct, 0);
+        assert_eq!(
+            h.check(7, 1, "201", 0),
+            Err(Refusal::Solved("200".to_string()))
+        );
+        assert_eq!(h.part(7, 1).unwrap().attempts.len(), 5);
+    }
+
+    #[test]
+    fn test_rate_limit() {
+        let mut h = History::default();
+        h.record(
+            3,
+            2,
+            "1",
+            Verdict::RateLimited(Some(Duration::from_secs(30))),
+            1000,
+        );
+        assert_eq!(h.check(5, 1, "1", 1010), Err(Refusal::RateLimited(20)));
+        assert_eq!(h.check(5, 1, "1", 1030), Ok(()));
+        h.record(3, 2, "1", Verdict::RateLimited(None), 2000);
+        assert_eq!(h.check(5, 1, "1", 2000), Err(Refusal::RateLimited(60)));
+        // Rate limited answers were never judged.
+        assert_eq!(h.part(3, 2).unwrap().wrong, Vec::<String>::new());
+    }
+
+    #[test]
+    fn test_submit_against_mock_server() {
+        let server = MockServer::start(|req| {
+            let message = if req.body.ends_with("answer=500") {
+                "That's not the right answer; your answer is too high."
+            } else {
+                "That's the right answer!"
+            };
+            (
+                200,
+                format!("<main><article><p>{message}</p></article></main>"),
+            )
+        });
+        let config = Config {
+            session: Some("secret".to_string()),
+            contact: None,
+        };
+        let client = Client::new(Ureq::new(), &config)
+            .with_base_url(&server.url)
+            .with_throttle(Duration::ZERO);
+        let mut h = History::default();
+        for answer in ["500", "600", "400", "401"] {
+            if h.check(2, 1, answer, 0).is_ok() {
+                let verdict = client.submit(2, 1, answer).unwrap();
+                h.record(2, 1, answer, verdict, 0);
+            }
+        }
+        let bodies: Vec<_> = server.received().into_iter().map(|r| r.body).collect();
+        assert_eq!(bodies, ["level=1&answer=500", "level=1&answer=400"]);
+        assert_eq!(h.part(2, 1).unwrap().correct.as_deref(), Some("400"));
+    }
+
+    #[test]
+    fn test_roundtrip() {
+        let mut h = History::default();
+        h.record(7, 1, "100", Verdict::TooLow, 5);
+        h.record(7, 2, "abc", Verdict::Wrong, 6);
+        h.record(7, 2, "1", Verdict::RateLimited(None), 7);
+        let out = toml::to_string(&h).unwrap();
+        assert_eq!(History::parse(&out).unwrap(), h);
+    }
+}
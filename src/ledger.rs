@@ -0,0 +1,123 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+/// Default location of the ledger, relative to the repository root.
+pub const DEFAULT_PATH: &str = "answers.toml";
+
+/// Known answers, keyed by day and input file.
+///
+/// Stored as TOML with one table per day and input:
+///
+/// ```toml
+/// [day1."input/day1"]
+/// part1 = "11"
+/// part2 = "31"
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct Ledger {
+    days: BTreeMap<String, BTreeMap<String, Answers>>,
+}
+
+/// The answers of both parts for one input, either of which may be unknown.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Answers {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub part1: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub part2: Option<String>,
+}
+
+impl Answers {
+    pub fn part(&self, part: u8) -> Option<&str> {
+        match part {
+            1 => self.part1.as_deref(),
+            2 => self.part2.as_deref(),
+            _ => panic!("Invalid part {part}"),
+        }
+    }
+
+    fn part_mut(&mut self, part: u8) -> &mut Option<String> {
+        match part {
+            1 => &mut self.part1,
+            2 => &mut self.part2,
+            _ => panic!("Invalid part {part}"),
+        }
+    }
+}
+
+fn day_key(day: u32) -> String {
+    format!("day{day}")
+}
+
+impl Ledger {
+    pub fn parse(input: &str) -> Result<Self, toml::de::Error> {
+        toml::from_str(input)
+    }
+
+    /// Reads the ledger at `path`, returning an empty ledger if the file does not exist.
+    pub fn load(path: impl AsRef<Path>) -> io::Result<Self> {
+        match fs::read_to_string(path) {
+            Ok(f) => Self::parse(&f).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e)),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(e),
+        }
+    }
+
+    pub fn save(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        let out =
+            toml::to_string(self).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        fs::write(path, out)
+    }
+
+    /// The recorded answer of `part` for `day` run on `input`.
+    pub fn get(&self, day: u32, input: &str, part: u8) -> Option<&str> {
+        self.days.get(&day_key(day))?.get(input)?.part(part)
+    }
+
+    pub fn set(&mut self, day: u32, input: &str, part: u8, answer: String) {
+        *self
+            .days
+            .entry(day_key(day))
+            .or_default()
+            .entry(input.to_string())
+            .or_default()
+            .part_mut(part) = Some(answer);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    static INP: &str = r#"[day1."input/day1"]
+part1 = "11"
+part2 = "31"
+
+[day11."input/day11"]
+part1 = "55312"
+"#;
+
+    #[test]
+    fn test_parse() {
+        let l = Ledger::parse(INP).unwrap();
+        assert_eq!(l.get(1, "input/day1", 1), Some("11"));
+        assert_eq!(l.get(1, "input/day1", 2), Some("31"));
+        assert_eq!(l.get(11, "input/day11", 2), None);
+        assert_eq!(l.get(1, "other", 1), None);
+        assert_eq!(l.get(2, "input/day1", 1), None);
+    }
+
+    #[test]
+    fn test_roundtrip() {
+        let mut l = Ledger::default();
+        l.set(11, "input/day11", 1, "55312".to_string());
+        l.set(1, "input/day1", 2, "31".to_string());
+        l.set(1, "input/day1", 1, "11".to_string());
+        let out = toml::to_string(&l).unwrap();
+        assert_eq!(Ledger::parse(&out).unwrap(), l);
+        assert_eq!(out, INP);
+    }
+}
@@ -0,0 +1,20 @@
+pub mod bench;
+pub mod client;
+pub mod cycle;
+pub mod days;
+pub mod differential;
+pub mod examples;
+pub mod geometry;
+pub mod grid;
+pub mod history;
+pub mod ledger;
+pub mod output;
+pub mod parallel;
+pub mod parse;
+pub mod render;
+pub mod rng;
+pub mod scaffold;
+pub mod search;
+pub mod solution;
+pub mod stepper;
+pub mod table;
@@ -0,0 +1,151 @@
+use serde::Serialize;
+use std::fmt::Write;
+
+/// How `aoc run` prints its answers.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, clap::ValueEnum)]
+pub enum Format {
+    /// One human readable line per answer
+    #[default]
+    Text,
+    /// A JSON array of records
+    Json,
+    /// Comma separated values with a header row
+    Csv,
+    /// Tab separated values with a header row
+    Tsv,
+}
+
+/// The answer to one part of one day on one input.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct Record {
+    pub day: u32,
+    pub part: u8,
+    pub answer: String,
+    pub input: String,
+    /// Time spent solving the part, without parsing.
+    pub time_ns: u64,
+}
+
+const FIELDS: [&str; 5] = ["day", "part", "answer", "input", "time_ns"];
+
+impl Record {
+    fn fields(&self) -> [String; 5] {
+        [
+            self.day.to_string(),
+            self.part.to_string(),
+            self.answer.clone(),
+            self.input.clone(),
+            self.time_ns.to_string(),
+        ]
+    }
+}
+
+/// Quotes a CSV field if it contains a separator, quote or line break.
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+/// Escapes the characters TSV cannot hold literally.
+fn tsv_field(field: &str) -> String {
+    field
+        .replace('\\', "\\\\")
+        .replace('\t', "\\t")
+        .replace('\n', "\\n")
+        .replace('\r', "\\r")
+}
+
+/// Renders `records` in `format`.
+///
+/// `labelled` adds the input to the text format, which is otherwise left out
+/// to keep the common single input case short.
+pub fn render(records: &[Record], format: Format, labelled: bool) -> String {
+    let mut out = String::new();
+    let separated = |out: &mut String, sep: &str, escape: fn(&str) -> String| {
+        let rows =
+            std::iter::once(FIELDS.map(String::from)).chain(records.iter().map(Record::fields));
+        for row in rows {
+            let row: Vec<_> = row.iter().map(|f| escape(f)).collect();
+            out.push_str(&row.join(sep));
+            out.push('\n');
+        }
+    };
+    match format {
+        Format::Text => {
+            for r in records {
+                let label = if labelled {
+                    format!(" ({})", r.input)
+                } else {
+                    String::new()
+                };
+                writeln!(out, "Day {} part {}{label}: {}", r.day, r.part, r.answer).unwrap();
+            }
+        }
+        Format::Json => {
+            out = serde_json::to_string_pretty(records).expect("Records are serializable");
+            out.push('\n');
+        }
+        Format::Csv => separated(&mut out, ",", csv_field),
+        Format::Tsv => separated(&mut out, "\t", tsv_field),
+    }
+    out
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn records() -> Vec<Record> {
+        vec![
+            Record {
+                day: 7,
+                part: 1,
+                answer: "3749".to_string(),
+                input: "input/day7".to_string(),
+                time_ns: 1200,
+            },
+            Record {
+                day: 7,
+                part: 2,
+                answer: "a,\"b\"".to_string(),
+                input: "in\tput".to_string(),
+                time_ns: 5,
+            },
+        ]
+    }
+
+    #[test]
+    fn test_text() {
+        let out = render(&records()[..1], Format::Text, false);
+        assert_eq!(out, "Day 7 part 1: 3749\n");
+        let out = render(&records()[..1], Format::Text, true);
+        assert_eq!(out, "Day 7 part 1 (input/day7): 3749\n");
+    }
+
+    #[test]
+    fn test_json() {
+        let out = render(&records(), Format::Json, false);
+        let value: serde_json::Value = serde_json::from_str(&out).unwrap();
+        assert_eq!(value[0]["day"], 7);
+        assert_eq!(value[0]["answer"], "3749");
+        assert_eq!(value[1]["input"], "in\tput");
+        assert_eq!(value[1]["time_ns"], 5);
+    }
+
+    #[test]
+    fn test_separated() {
+        let out = render(&records(), Format::Csv, false);
+        assert_eq!(
+            out,
+            "day,part,answer,input,time_ns\n7,1,3749,input/day7,1200\n7,2,\"a,\"\"b\"\"\",in\tput,5\n"
+        );
+        let out = render(&records(), Format::Tsv, false);
+        assert_eq!(
+            out,
+            "day\tpart\tanswer\tinput\ttime_ns\n7\t1\t3749\tinput/day7\t1200\n7\t2\ta,\"b\"\tin\\tput\t5\n"
+        );
+    }
+}
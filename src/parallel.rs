@@ -0,0 +1,184 @@
+use crate::solution::{Answer, DynSolution};
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// Runs `f`, turning a panic into its message.
+pub fn catch<T>(f: impl FnOnce() -> T) -> Result<T, String> {
+    panic::catch_unwind(AssertUnwindSafe(f)).map_err(|e| {
+        e.downcast_ref::<&str>()
+            .map(|s| s.to_string())
+            .or_else(|| e.downcast_ref::<String>().cloned())
+            .unwrap_or_default()
+    })
+}
+
+/// One input of one day to solve.
+pub struct Job {
+    pub day: &'static dyn DynSolution,
+    /// Names the input, e.g. its path.
+    pub key: String,
+    pub input: String,
+}
+
+/// The outcome of one part, with a panic turned into `Err` with its message.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Solved {
+    pub part: u8,
+    pub answer: Result<Answer, String>,
+    pub time: Duration,
+}
+
+/// The outcome of a [`Job`], `Err` if its input could not be parsed.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Finished {
+    pub day: u32,
+    pub key: String,
+    pub parts: Result<Vec<Solved>, String>,
+}
+
+fn solve(job: &Job, parts: &[u8]) -> Finished {
+    let parsed = match catch(|| job.day.parse(&job.input)) {
+        Ok(Ok(parsed)) => Ok(parsed),
+        Ok(Err(e)) => Err(e.diagnostic()),
+        Err(msg) => Err(format!("parsing panicked: {msg}")),
+    };
+    let parts = parsed.map(|parsed| {
+        parts
+            .iter()
+            .map(|&part| {
+                let start = Instant::now();
+                let answer = catch(|| match part {
+                    1 => parsed.part1(),
+                    _ => parsed.part2(),
+                });
+                Solved {
+                    part,
+                    answer,
+                    time: start.elapsed(),
+                }
+            })
+            .collect()
+    });
+    Finished {
+        day: job.day.day(),
+        key: job.key.clone(),
+        parts,
+    }
+}
+
+/// Solves `parts` of every job on `threads` threads, each part isolated from
+/// panics in the others.
+///
+/// Returns the outcomes in the order of `jobs`.
+pub fn solve_all(jobs: &[Job], parts: &[u8], threads: usize) -> Vec<Finished> {
+    let next = AtomicUsize::new(0);
+    let results = Mutex::new(vec![None; jobs.len()]);
+    thread::scope(|s| {
+        for _ in 0..threads.clamp(1, jobs.len().max(1)) {
+            s.spawn(|| loop {
+                let i = next.fetch_add(1, Ordering::Relaxed);
+                let Some(job) = jobs.get(i) else {
+                    break;
+                };
+                let finished = solve(job, parts);
+                results.lock().unwrap()[i] = Some(finished);
+            });
+        }
+    });
+    results
+        .into_inner()
+        .unwrap()
+        .into_iter()
+        .map(|r| r.expect("Every job is solved"))
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::parse::ParseError;
+    use crate::solution::Solution;
+
+    /// Sums numbers in part 1 and panics on empty inputs in part 2.
+    struct Fragile;
+
+    impl Solution for Fragile {
+        const DAY: u32 = 0;
+        type Input = Vec<u64>;
+
+        fn parse(input: &str) -> Result<Self::Input, ParseError> {
+            input
+                .split_whitespace()
+                .map(|n| n.parse().map_err(|_| ParseError::at(input, n, "a number")))
+                .collect()
+        }
+
+        fn part1(input: &Self::Input) -> Answer {
+            input.iter().sum::<u64>().into()
+        }
+
+        fn part2(input: &Self::Input) -> Answer {
+            input.iter().max().expect("No numbers").to_string().into()
+        }
+    }
+
+    fn job(input: &str) -> Job {
+        Job {
+            day: &Fragile,
+            key: input.to_string(),
+            input: input.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_catch() {
+        assert_eq!(catch(|| 5), Ok(5));
+        assert_eq!(
+            catch(|| panic!("static")),
+            Err::<(), _>("static".to_string())
+        );
+        let n = 3;
+        assert_eq!(
+            catch(|| panic!("formatted {n}")),
+            Err::<(), _>("formatted 3".to_string())
+        );
+    }
+
+    #[test]
+    fn test_solve_all() {
+        let jobs: Vec<_> = ["1 2", "", "x", "4 5 6"].into_iter().map(job).collect();
+        let finished = solve_all(&jobs, &[1, 2], 3);
+        let answers: Vec<_> = finished
+            .iter()
+            .map(|f| {
+                f.parts.as_ref().map(|parts| {
+                    parts
+                        .iter()
+                        .map(|s| s.answer.clone().map(|a| a.to_string()))
+                        .collect::<Vec<_>>()
+                })
+            })
+            .collect();
+        assert_eq!(
+            answers[0],
+            Ok(vec![Ok("3".to_string()), Ok("2".to_string())])
+        );
+        assert_eq!(
+            answers[1],
+            Ok(vec![Ok("0".to_string()), Err("No numbers".to_string())])
+        );
+        assert!(answers[2].is_err());
+        assert_eq!(
+            answers[3],
+            Ok(vec![Ok("15".to_string()), Ok("6".to_string())])
+        );
+        assert_eq!(finished[3].key, "4 5 6");
+
+        let only_part1 = solve_all(&jobs[..1], &[1], 8);
+        assert_eq!(only_part1[0].parts.as_ref().unwrap().len(), 1);
+        assert!(solve_all(&[], &[1, 2], 4).is_empty());
+    }
+}
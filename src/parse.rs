@@ -0,0 +1,197 @@
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+
+/// Error returned when a puzzle input cannot be parsed, pointing at the offending text.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParseError {
+    /// Day whose parser failed, filled in by the day registry.
+    pub day: Option<u32>,
+    /// 1-based line number.
+    pub line: usize,
+    /// 1-based column, counted in characters.
+    pub column: usize,
+    /// The whole line containing the error.
+    pub source_line: String,
+    /// The offending text, empty at the end of a line or of the input.
+    pub text: String,
+    /// What the parser expected instead.
+    pub expected: String,
+}
+
+impl ParseError {
+    /// Creates an error for `part`, which must be a slice of `input`.
+    pub fn at(input: &str, part: &str, expected: impl Into<String>) -> Self {
+        let offset = offset_in(input, part);
+        let line_start = input[..offset].rfind('\n').map_or(0, |i| i + 1);
+        let line_end = input[offset..]
+            .find('\n')
+            .map_or(input.len(), |i| offset + i);
+        Self {
+            day: None,
+            line: input[..line_start].matches('\n').count() + 1,
+            column: input[line_start..offset].chars().count() + 1,
+            source_line: input[line_start..line_end]
+                .trim_end_matches('\r')
+                .to_string(),
+            text: part.to_string(),
+            expected: expected.into(),
+        }
+    }
+
+    pub fn with_day(mut self, day: u32) -> Self {
+        self.day = Some(day);
+        self
+    }
+
+    /// Renders the error followed by the source line with the offending text underlined.
+    pub fn diagnostic(&self) -> String {
+        let number = self.line.to_string();
+        let pad = " ".repeat(number.len());
+        let indent = " ".repeat(self.column - 1);
+        let carets = "^".repeat(self.text.chars().count().max(1));
+        format!(
+            "{self}\n{pad} |\n{number} | {}\n{pad} | {indent}{carets}",
+            self.source_line
+        )
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(day) = self.day {
+            write!(f, "day {day}, ")?;
+        }
+        write!(
+            f,
+            "line {}, column {}: expected {}, found ",
+            self.line, self.column, self.expected
+        )?;
+        if self.text.is_empty() {
+            write!(f, "end of line")
+        } else {
+            write!(f, "{:?}", self.text)
+        }
+    }
+}
+
+impl Error for ParseError {}
+
+/// Byte offset of `part` within `input`.
+fn offset_in(input: &str, part: &str) -> usize {
+    let offset = (part.as_ptr() as usize).wrapping_sub(input.as_ptr() as usize);
+    assert!(offset <= input.len(), "{part:?} is not part of the input");
+    offset
+}
+
+/// A line of puzzle input that knows where it came from, to build positioned errors.
+#[derive(Clone, Copy, Debug)]
+pub struct Line<'a> {
+    pub input: &'a str,
+    pub text: &'a str,
+}
+
+/// The lines of `input`, like [`str::lines`].
+pub fn lines(input: &str) -> impl Iterator<Item = Line<'_>> {
+    input.lines().map(move |text| Line { input, text })
+}
+
+impl<'a> Line<'a> {
+    /// Creates an error for `part`, which must be a slice of this line.
+    pub fn error(&self, part: &str, expected: impl Into<String>) -> ParseError {
+        ParseError::at(self.input, part, expected)
+    }
+
+    /// Creates an error pointing just past the end of this line.
+    pub fn error_at_end(&self, expected: impl Into<String>) -> ParseError {
+        self.error(&self.text[self.text.len()..], expected)
+    }
+
+    /// Parses `part`, which must be a slice of this line.
+    pub fn parse<T: FromStr>(&self, part: &str, expected: &str) -> Result<T, ParseError> {
+        part.parse().map_err(|_| self.error(part, expected))
+    }
+
+    /// Takes the next token from `tokens`, failing at the end of the line if there is none.
+    pub fn next(
+        &self,
+        tokens: &mut impl Iterator<Item = &'a str>,
+        expected: &str,
+    ) -> Result<&'a str, ParseError> {
+        tokens.next().ok_or_else(|| self.error_at_end(expected))
+    }
+
+    /// Parses the next token from `tokens`.
+    pub fn parse_next<T: FromStr>(
+        &self,
+        tokens: &mut impl Iterator<Item = &'a str>,
+        expected: &str,
+    ) -> Result<T, ParseError> {
+        let token = self.next(tokens, expected)?;
+        self.parse(token, expected)
+    }
+
+    /// Fails if `tokens` has anything left.
+    pub fn end(&self, tokens: &mut impl Iterator<Item = &'a str>) -> Result<(), ParseError> {
+        match tokens.next() {
+            Some(token) => Err(self.error(token, "end of line")),
+            None => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    static INP: &str = "1 2
+3 x 4
+";
+
+    #[test]
+    fn test_at() {
+        let err = ParseError::at(INP, &INP[6..7], "a number");
+        assert_eq!(err.line, 2);
+        assert_eq!(err.column, 3);
+        assert_eq!(err.source_line, "3 x 4");
+        assert_eq!(err.text, "x");
+        assert_eq!(
+            err.with_day(7).to_string(),
+            "day 7, line 2, column 3: expected a number, found \"x\""
+        );
+    }
+
+    #[test]
+    fn test_diagnostic() {
+        let err = ParseError::at(INP, &INP[6..7], "a number");
+        assert_eq!(
+            err.diagnostic(),
+            "line 2, column 3: expected a number, found \"x\"
+  |
+2 | 3 x 4
+  |   ^"
+        );
+    }
+
+    #[test]
+    fn test_line() {
+        let mut lines = lines(INP);
+        let first = lines.next().unwrap();
+        let mut it = first.text.split(' ');
+        assert_eq!(first.parse_next::<u64>(&mut it, "a number"), Ok(1));
+        assert_eq!(first.parse_next::<u64>(&mut it, "a number"), Ok(2));
+        let err = first.next(&mut it, "a number").unwrap_err();
+        assert_eq!((err.line, err.column), (1, 4));
+        assert_eq!(
+            err.to_string(),
+            "line 1, column 4: expected a number, found end of line"
+        );
+
+        let second = lines.next().unwrap();
+        let mut it = second.text.split(' ');
+        assert_eq!(second.parse_next::<u64>(&mut it, "a number"), Ok(3));
+        let err = second.parse_next::<u64>(&mut it, "a number").unwrap_err();
+        assert_eq!((err.line, err.column), (2, 3));
+        let err = second.end(&mut it).unwrap_err();
+        assert_eq!((err.column, err.text.as_str()), (5, "4"));
+    }
+}
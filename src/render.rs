@@ -0,0 +1,396 @@
+use crate::grid::Grid;
+use std::collections::HashMap;
+use std::fs::{self, File};
+use std::io::{self, BufWriter, Write};
+use std::path::{Path, PathBuf};
+
+/// An RGB color.
+pub type Color = [u8; 3];
+
+pub const BLACK: Color = [0, 0, 0];
+pub const WHITE: Color = [255, 255, 255];
+pub const GREY: Color = [110, 110, 110];
+pub const DARK: Color = [24, 24, 32];
+pub const RED: Color = [230, 50, 40];
+pub const GREEN: Color = [60, 200, 80];
+pub const BLUE: Color = [60, 110, 230];
+pub const YELLOW: Color = [250, 210, 40];
+
+/// Frames of a visualisation, each one a grid of cell colors.
+pub type Frames<'a> = Box<dyn Iterator<Item = Grid<Color>> + 'a>;
+
+/// A bright color for the `i`th of many things, neighboring indices getting
+/// clearly different hues.
+pub fn hue(i: u32) -> Color {
+    // Stepping by the golden ratio spreads any number of hues evenly.
+    let h = (i as f64 * 0.618_033_988_75).fract() * 6.0;
+    let x = 1.0 - (h % 2.0 - 1.0).abs();
+    let (r, g, b) = match h as u32 {
+        0 => (1.0, x, 0.0),
+        1 => (x, 1.0, 0.0),
+        2 => (0.0, 1.0, x),
+        3 => (0.0, x, 1.0),
+        4 => (x, 0.0, 1.0),
+        _ => (1.0, 0.0, x),
+    };
+    let channel = |c: f64| (55.0 + c * 200.0) as u8;
+    [channel(r), channel(g), channel(b)]
+}
+
+/// Mixes `a` and `b`, `t` going from 0 for `a` to 1 for `b`.
+pub fn blend(a: Color, b: Color, t: f64) -> Color {
+    let t = t.clamp(0.0, 1.0);
+    std::array::from_fn(|i| (a[i] as f64 * (1.0 - t) + b[i] as f64 * t).round() as u8)
+}
+
+/// An RGB raster image.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Image {
+    width: usize,
+    height: usize,
+    /// Row-major RGB triples.
+    pixels: Vec<u8>,
+}
+
+impl Image {
+    /// Draws every cell of `grid` as a `scale` by `scale` square.
+    pub fn from_grid(grid: &Grid<Color>, scale: usize) -> Self {
+        let scale = scale.max(1);
+        let width = grid.width() * scale;
+        let mut pixels = Vec::with_capacity(width * grid.height() * scale * 3);
+        for row in grid.rows() {
+            let mut line = Vec::with_capacity(width * 3);
+            for color in row {
+                for _ in 0..scale {
+                    line.extend_from_slice(color);
+                }
+            }
+            for _ in 0..scale {
+                pixels.extend_from_slice(&line);
+            }
+        }
+        Self {
+            width,
+            height: grid.height() * scale,
+            pixels,
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn pixel(&self, x: usize, y: usize) -> Color {
+        let i = (y * self.width + x) * 3;
+        [self.pixels[i], self.pixels[i + 1], self.pixels[i + 2]]
+    }
+
+    /// Writes a binary PPM, the simplest format most image viewers open.
+    pub fn write_ppm(&self, mut out: impl Write) -> io::Result<()> {
+        write!(out, "P6\n{} {}\n255\n", self.width, self.height)?;
+        out.write_all(&self.pixels)
+    }
+
+    pub fn write_png(&self, out: impl Write) -> io::Result<()> {
+        let mut encoder = png::Encoder::new(out, self.width as u32, self.height as u32);
+        encoder.set_color(png::ColorType::Rgb);
+        encoder.set_depth(png::BitDepth::Eight);
+        let mut writer = encoder.write_header().map_err(io::Error::other)?;
+        writer
+            .write_image_data(&self.pixels)
+            .map_err(io::Error::other)?;
+        writer.finish().map_err(io::Error::other)
+    }
+
+    /// The image as a GIF frame, with an exact palette if it has at most 256
+    /// colors and a quantized one otherwise.
+    fn gif_frame(&self) -> io::Result<gif::Frame<'static>> {
+        let (width, height) = (gif_size(self.width)?, gif_size(self.height)?);
+        let mut palette: HashMap<&[u8], u8> = HashMap::new();
+        let mut indices = Vec::with_capacity(self.width * self.height);
+        for color in self.pixels.chunks_exact(3) {
+            let next = palette.len();
+            let i = match palette.get(color) {
+                Some(&i) => i,
+                None if next == 256 => {
+                    return Ok(gif::Frame::from_rgb_speed(width, height, &self.pixels, 10));
+                }
+                None => {
+                    palette.insert(color, next as u8);
+                    next as u8
+                }
+            };
+            indices.push(i);
+        }
+        let mut colors = vec![0; palette.len() * 3];
+        for (color, i) in palette {
+            colors[i as usize * 3..][..3].copy_from_slice(color);
+        }
+        Ok(gif::Frame::from_palette_pixels(
+            width, height, indices, colors, None,
+        ))
+    }
+}
+
+fn gif_size(n: usize) -> io::Result<u16> {
+    u16::try_from(n).map_err(|_| {
+        io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("{n} pixels are too many for a GIF"),
+        )
+    })
+}
+
+/// Writes `frames` as a looping GIF animation, showing each for `delay`
+/// hundredths of a second.
+///
+/// Returns the number of frames written.
+pub fn write_gif(
+    frames: impl IntoIterator<Item = Image>,
+    delay: u16,
+    out: impl Write,
+) -> io::Result<usize> {
+    let mut frames = frames.into_iter().peekable();
+    let Some(first) = frames.peek() else {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "No frames to animate",
+        ));
+    };
+    let mut encoder = gif::Encoder::new(out, gif_size(first.width)?, gif_size(first.height)?, &[])
+        .map_err(io::Error::other)?;
+    encoder
+        .set_repeat(gif::Repeat::Infinite)
+        .map_err(io::Error::other)?;
+    let mut written = 0;
+    for image in frames {
+        let mut frame = image.gif_frame()?;
+        frame.delay = delay;
+        encoder.write_frame(&frame).map_err(io::Error::other)?;
+        written += 1;
+    }
+    Ok(written)
+}
+
+/// Image formats, chosen by file extension.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Format {
+    Ppm,
+    Png,
+    Gif,
+}
+
+impl Format {
+    pub fn from_path(path: &Path) -> Option<Self> {
+        match path.extension()?.to_str()?.to_ascii_lowercase().as_str() {
+            "ppm" => Some(Format::Ppm),
+            "png" => Some(Format::Png),
+            "gif" => Some(Format::Gif),
+            _ => None,
+        }
+    }
+}
+
+/// How to turn frames into a file.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Export {
+    /// Side length in pixels of one grid cell.
+    pub scale: usize,
+    /// Only every `every`th frame ends up in animations, plus the last one.
+    pub every: usize,
+    /// Hundredths of a second each frame of an animation is shown.
+    pub delay: u16,
+}
+
+impl Default for Export {
+    fn default() -> Self {
+        Self {
+            scale: 4,
+            every: 1,
+            delay: 4,
+        }
+    }
+}
+
+/// Every `every`th item of `items`, always including the last one.
+fn sample<T>(items: impl Iterator<Item = T>, every: usize) -> impl Iterator<Item = T> {
+    let every = every.max(1);
+    let mut items = items.enumerate().peekable();
+    std::iter::from_fn(move || loop {
+        let (i, item) = items.next()?;
+        if i % every == 0 || items.peek().is_none() {
+            return Some(item);
+        }
+    })
+}
+
+/// A file being written under a temporary name next to its destination, so the
+/// destination only ever holds complete images.
+///
+/// Dropping it without [`Pending::persist`] removes the temporary file.
+struct Pending {
+    tmp: PathBuf,
+    persisted: bool,
+}
+
+impl Pending {
+    fn new(path: &Path) -> Self {
+        let mut name = std::ffi::OsString::from(".");
+        name.push(path.file_name().unwrap_or_default());
+        name.push(format!(".{}.tmp", std::process::id()));
+        Self {
+            tmp: path.with_file_name(name),
+            persisted: false,
+        }
+    }
+
+    /// Moves the finished temporary file to `path`.
+    fn persist(mut self, path: &Path) -> io::Result<()> {
+        fs::rename(&self.tmp, path)?;
+        self.persisted = true;
+        Ok(())
+    }
+}
+
+impl Drop for Pending {
+    fn drop(&mut self) {
+        if !self.persisted {
+            let _ = fs::remove_file(&self.tmp);
+        }
+    }
+}
+
+/// Writes `frames` to `path` in the format its extension asks for.
+///
+/// Still images show the last frame, GIFs animate them. Returns the number of
+/// frames written. `path` is left untouched unless every frame was written.
+pub fn export(
+    frames: impl Iterator<Item = Grid<Color>>,
+    path: &Path,
+    options: Export,
+) -> io::Result<usize> {
+    let format = Format::from_path(path).ok_or_else(|| {
+        io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("{} is not a .ppm, .png or .gif file", path.display()),
+        )
+    })?;
+    let pending = Pending::new(path);
+    let mut out = BufWriter::new(File::create(&pending.tmp)?);
+    let written = match format {
+        Format::Ppm | Format::Png => {
+            let last = frames
+                .last()
+                .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "Nothing to draw"))?;
+            let image = Image::from_grid(&last, options.scale);
+            if format == Format::Ppm {
+                image.write_ppm(&mut out)?;
+            } else {
+                image.write_png(&mut out)?;
+            }
+            1
+        }
+        Format::Gif => {
+            let images = sample(frames, options.every).map(|g| Image::from_grid(&g, options.scale));
+            write_gif(images, options.delay, &mut out)?
+        }
+    };
+    out.into_inner().map_err(|e| e.into_error())?.sync_all()?;
+    pending.persist(path)?;
+    Ok(written)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn checkerboard() -> Grid<Color> {
+        Grid::from_rows(vec![vec![RED, BLUE], vec![BLUE, RED]])
+    }
+
+    #[test]
+    fn test_from_grid() {
+        let image = Image::from_grid(&checkerboard(), 3);
+        assert_eq!((image.width(), image.height()), (6, 6));
+        assert_eq!(image.pixel(2, 2), RED);
+        assert_eq!(image.pixel(3, 2), BLUE);
+        assert_eq!(image.pixel(5, 5), RED);
+    }
+
+    #[test]
+    fn test_ppm() {
+        let mut out = Vec::new();
+        Image::from_grid(&checkerboard(), 1)
+            .write_ppm(&mut out)
+            .unwrap();
+        assert!(out.starts_with(b"P6\n2 2\n255\n"));
+        assert_eq!(&out[11..], [RED, BLUE, BLUE, RED].concat());
+    }
+
+    #[test]
+    fn test_png_and_gif_headers() {
+        let image = Image::from_grid(&checkerboard(), 2);
+        let mut png = Vec::new();
+        image.write_png(&mut png).unwrap();
+        assert!(png.starts_with(b"\x89PNG\r\n\x1a\n"));
+
+        let mut gif = Vec::new();
+        let frames = vec![image.clone(), Image::from_grid(&checkerboard(), 2)];
+        assert_eq!(write_gif(frames, 5, &mut gif).unwrap(), 2);
+        assert!(gif.starts_with(b"GIF89a"));
+        assert_eq!(gif.last(), Some(&0x3b));
+        assert!(write_gif(Vec::new(), 5, &mut Vec::new()).is_err());
+    }
+
+    #[test]
+    fn test_many_colors_gif() {
+        let colors: Vec<_> = (0..400).map(|i| [i as u8, (i / 2) as u8, 7]).collect();
+        let image = Image::from_grid(&Grid::from_vec(20, 20, colors), 1);
+        let mut gif = Vec::new();
+        assert_eq!(write_gif([image], 1, &mut gif).unwrap(), 1);
+    }
+
+    #[test]
+    fn test_export() {
+        let dir = std::env::temp_dir().join(format!("aoc-render-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let options = Export::default();
+
+        let path = dir.join("board.gif");
+        let frames = vec![checkerboard(), checkerboard()];
+        assert_eq!(export(frames.into_iter(), &path, options).unwrap(), 2);
+        assert!(fs::read(&path).unwrap().starts_with(b"GIF89a"));
+
+        // Failed exports leave neither a new file nor a truncated old one.
+        let empty = dir.join("empty.png");
+        assert!(export(std::iter::empty(), &empty, options).is_err());
+        assert!(!empty.exists());
+        let before = fs::read(&path).unwrap();
+        assert!(export(std::iter::empty(), &path, options).is_err());
+        assert_eq!(fs::read(&path).unwrap(), before);
+        assert_eq!(fs::read_dir(&dir).unwrap().count(), 1);
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_sample() {
+        assert_eq!(sample(0..10, 4).collect::<Vec<_>>(), [0, 4, 8, 9]);
+        assert_eq!(sample(0..9, 4).collect::<Vec<_>>(), [0, 4, 8]);
+        assert_eq!(sample(0..3, 0).collect::<Vec<_>>(), [0, 1, 2]);
+    }
+
+    #[test]
+    fn test_colors() {
+        assert_ne!(hue(0), hue(1));
+        assert_eq!(blend(BLACK, WHITE, 0.0), BLACK);
+        assert_eq!(blend(BLACK, WHITE, 1.0), WHITE);
+        assert_eq!(blend(BLACK, WHITE, 0.5), [128, 128, 128]);
+        assert_eq!(Format::from_path(Path::new("walk.GIF")), Some(Format::Gif));
+        assert_eq!(Format::from_path(Path::new("walk.txt")), None);
+    }
+}
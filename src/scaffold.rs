@@ -0,0 +1,309 @@
+use crate::examples;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const TEMPLATE: &str = include_str!("../templates/day.rs.tmpl");
+
+/// Location of the day registry, relative to the repository root.
+pub const REGISTRY: &str = "src/days/mod.rs";
+
+/// Source of a new day module, implementing [`Solution`](crate::solution::Solution)
+/// with placeholder parts.
+pub fn day_module(day: u32) -> String {
+    TEMPLATE.replace("{{DAY}}", &day.to_string())
+}
+
+/// Expected answers of a new day's example, with a placeholder that keeps its
+/// test failing until the real answer is filled in.
+pub fn expected_answers() -> &'static str {
+    "[\"example.txt\"]\npart1 = \"TODO\"\n"
+}
+
+/// The crate root at or above `start`, the first directory with a `Cargo.toml`
+/// and a day registry.
+pub fn find_root(start: &Path) -> Option<PathBuf> {
+    start
+        .ancestors()
+        .find(|dir| dir.join("Cargo.toml").is_file() && dir.join(REGISTRY).is_file())
+        .map(Path::to_path_buf)
+}
+
+/// Adds `dayN::DayN` to the `days!` invocation in `registry`, the source of
+/// the registry module, keeping the days in order.
+///
+/// Returns `None` if there is no `days!` invocation or the day is already in it.
+pub fn register(registry: &str, day: u32) -> Option<String> {
+    let start = registry.find("\ndays!(\n")? + "\ndays!(\n".len();
+    let end = start + registry[start..].find("\n);")?;
+    let mut entries: Vec<(u32, String)> = registry[start..end]
+        .lines()
+        .map(|l| {
+            let n = l
+                .trim()
+                .strip_prefix("day")?
+                .split("::")
+                .next()?
+                .parse()
+                .ok()?;
+            Some((n, l.to_string()))
+        })
+        .collect::<Option<_>>()?;
+    if entries.iter().any(|(n, _)| *n == day) {
+        return None;
+    }
+    entries.push((day, format!("    day{day}::Day{day},")));
+    entries.sort();
+    let lines: Vec<_> = entries.into_iter().map(|(_, l)| l).collect();
+    Some(format!(
+        "{}{}{}",
+        &registry[..start],
+        lines.join("\n"),
+        &registry[end..]
+    ))
+}
+
+/// Creates the module, example fixture and empty input of `day` below `root`
+/// and registers the day, returning the created files.
+///
+/// Refuses to touch anything if the day module already exists, and keeps
+/// example fixtures and inputs that are already there. Removes whatever it
+/// created if any step fails.
+pub fn create(root: &Path, input_dir: &Path, day: u32) -> Result<Vec<PathBuf>, String> {
+    let module = root.join(format!("src/days/day{day}.rs"));
+    if module.exists() {
+        return Err(format!("{} already exists", module.display()));
+    }
+    let registry_path = root.join(REGISTRY);
+    let registry = fs::read_to_string(&registry_path)
+        .map_err(|e| format!("Cannot read {}: {e}", registry_path.display()))?;
+    let registry = register(&registry, day).ok_or_else(|| {
+        format!(
+            "Cannot register day {day} in {}, is it already there?",
+            registry_path.display()
+        )
+    })?;
+
+    let example_dir = root.join(examples::dir(day));
+    let input = root.join(input_dir).join(format!("day{day}"));
+    let mut files = vec![(module, day_module(day))];
+    // Never clobber fixtures or an input prepared before the day was started.
+    for (path, contents) in [
+        (example_dir.join("example.txt"), String::new()),
+        (
+            example_dir.join("expected.toml"),
+            expected_answers().to_string(),
+        ),
+        (input, String::new()),
+    ] {
+        if !path.exists() {
+            files.push((path, contents));
+        }
+    }
+    // Undo everything written so far if a later step fails, so that a rerun
+    // does not trip over leftovers.
+    let mut created = Vec::new();
+    let mut dirs = Vec::new();
+    let res = files
+        .into_iter()
+        .try_for_each(|(path, contents)| write_new(path, &contents, &mut created, &mut dirs))
+        .and_then(|()| {
+            fs::write(&registry_path, registry)
+                .map_err(|e| format!("Cannot write {}: {e}", registry_path.display()))
+        });
+    if let Err(e) = res {
+        for path in created.iter().rev() {
+            let _ = fs::remove_file(path);
+        }
+        for dir in dirs.iter().rev() {
+            let _ = fs::remove_dir(dir);
+        }
+        return Err(e);
+    }
+    created.push(registry_path);
+    Ok(created)
+}
+
+/// Writes `contents` to the new file `path`, recording it and every directory
+/// created for it.
+fn write_new(
+    path: PathBuf,
+    contents: &str,
+    created: &mut Vec<PathBuf>,
+    dirs: &mut Vec<PathBuf>,
+) -> Result<(), String> {
+    if let Some(dir) = path.parent() {
+        let mut missing: Vec<_> = dir.ancestors().take_while(|d| !d.exists()).collect();
+        missing.reverse();
+        for d in missing {
+            fs::create_dir(d).map_err(|e| format!("Cannot create {}: {e}", d.display()))?;
+            dirs.push(d.to_path_buf());
+        }
+    }
+    fs::write(&path, contents).map_err(|e| format!("Cannot write {}: {e}", path.display()))?;
+    created.push(path);
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    static INP: &str = "use crate::solution::DynSolution;
+
+days!(
+    day1::Day1,
+    day2::Day2,
+    day10::Day10,
+);
+
+pub fn get(day: u32) {}
+";
+
+    #[test]
+    fn test_register() {
+        let res = register(INP, 3).unwrap();
+        assert!(res.contains("    day2::Day2,\n    day3::Day3,\n    day10::Day10,\n);\n"));
+        let res = register(&res, 12).unwrap();
+        assert!(res.contains("    day10::Day10,\n    day12::Day12,\n);\n"));
+        assert!(res.ends_with("pub fn get(day: u32) {}\n"));
+        assert_eq!(register(INP, 2), None);
+        assert_eq!(register("fn main() {}", 2), None);
+    }
+
+    #[test]
+    fn test_registry_is_recognised() {
+        let registry = fs::read_to_string(REGISTRY).unwrap();
+        assert!(register(&registry, 99).is_some());
+    }
+
+    #[test]
+    fn test_find_root() {
+        let manifest = Path::new(env!("CARGO_MANIFEST_DIR"));
+        assert_eq!(
+            find_root(&manifest.join("src/days")).as_deref(),
+            Some(manifest)
+        );
+        assert_eq!(
+            find_root(&manifest.join("no/such/dir")).as_deref(),
+            Some(manifest)
+        );
+        assert_eq!(find_root(Path::new("/")), None);
+    }
+
+    #[test]
+    fn test_day_module() {
+        let module = day_module(12);
+        assert!(module.contains("pub struct Day12;"));
+        assert!(module.contains("const DAY: u32 = 12;"));
+        assert!(!module.contains("{{"));
+    }
+
+    #[test]
+    fn test_create() {
+        let root = std::env::temp_dir().join(format!("aoc-scaffold-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        fs::create_dir_all(root.join("src/days")).unwrap();
+        fs::write(root.join(REGISTRY), INP).unwrap();
+        fs::create_dir_all(root.join("input")).unwrap();
+        fs::write(root.join("input/day12"), "fetched").unwrap();
+        fs::create_dir_all(root.join("examples/day12")).unwrap();
+        fs::write(root.join("examples/day12/example.txt"), "pasted").unwrap();
+
+        let created = create(&root, Path::new("input"), 12).unwrap();
+        assert_eq!(created.len(), 3);
+        assert_eq!(
+            fs::read_to_string(root.join("examples/day12/example.txt")).unwrap(),
+            "pasted"
+        );
+        assert_eq!(
+            fs::read_to_string(root.join("examples/day12/expected.toml")).unwrap(),
+            expected_answers()
+        );
+        assert_eq!(
+            fs::read_to_string(root.join("src/days/day12.rs")).unwrap(),
+            day_module(12)
+        );
+        assert_eq!(
+            fs::read_to_string(root.join("input/day12")).unwrap(),
+            "fetched"
+        );
+        assert!(fs::read_to_string(root.join(REGISTRY))
+            .unwrap()
+            .contains("day12::Day12,"));
+        assert!(create(&root, Path::new("input"), 12).is_err());
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn test_create_rolls_back() {
+        let root =
+            std::env::temp_dir().join(format!("aoc-scaffold-rollback-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        fs::create_dir_all(root.join("src/days")).unwrap();
+        fs::write(root.join(REGISTRY), INP).unwrap();
+        // The input cannot be written below a file, after everything else was.
+        fs::write(root.join("input"), "not a directory").unwrap();
+
+        assert!(create(&root, Path::new("input"), 12).is_err());
+        assert!(!root.join("src/days/day12.rs").exists());
+        assert!(!root.join("examples").exists());
+        assert_eq!(fs::read_to_string(root.join(REGISTRY)).unwrap(), INP);
+
+        fs::remove_file(root.join("input")).unwrap();
+        assert_eq!(create(&root, Path::new("input"), 12).unwrap().len(), 5);
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    fn copy_dir(from: &Path, to: &Path) {
+        fs::create_dir_all(to).unwrap();
+        for entry in fs::read_dir(from).unwrap().flatten() {
+            let target = to.join(entry.file_name());
+            if entry.file_type().unwrap().is_dir() {
+                copy_dir(&entry.path(), &target);
+            } else {
+                fs::copy(entry.path(), target).unwrap();
+            }
+        }
+    }
+
+    /// Scaffolds a day into a copy of this crate and runs its tests there, so
+    /// that only the new day's example tests fail.
+    #[test]
+    fn test_scaffolded_suite() {
+        let manifest = Path::new(env!("CARGO_MANIFEST_DIR"));
+        let root = std::env::temp_dir().join(format!("aoc-scaffold-suite-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        fs::create_dir_all(&root).unwrap();
+        for file in ["Cargo.toml", "Cargo.lock", "build.rs"] {
+            if manifest.join(file).exists() {
+                fs::copy(manifest.join(file), root.join(file)).unwrap();
+            }
+        }
+        for dir in ["src", "templates", "examples"] {
+            copy_dir(&manifest.join(dir), &root.join(dir));
+        }
+        let day = 25;
+        create(&root, Path::new("input"), day).unwrap();
+
+        let cargo_test = |filter: &[&str]| {
+            std::process::Command::new(env!("CARGO"))
+                .args(["test", "--offline", "--lib", "--"])
+                .args(filter)
+                .current_dir(&root)
+                // Share compiled dependencies between runs, apart from this crate's own.
+                .env("CARGO_TARGET_DIR", manifest.join("target/scaffold"))
+                .output()
+                .unwrap()
+        };
+        let name = format!("days::day{day}::");
+        let rest = cargo_test(&["--skip", &name, "--skip", "test_scaffolded_suite"]);
+        assert!(
+            rest.status.success(),
+            "{}",
+            String::from_utf8_lossy(&rest.stdout)
+        );
+        let new = cargo_test(&[&name]);
+        let stdout = String::from_utf8_lossy(&new.stdout);
+        assert!(stdout.contains("0 passed; 2 failed"), "{stdout}");
+        fs::remove_dir_all(&root).unwrap();
+    }
+}
@@ -0,0 +1,416 @@
+use std::cmp::Reverse;
+use std::collections::hash_map::Entry;
+use std::collections::{BinaryHeap, HashMap, HashSet, VecDeque};
+use std::hash::Hash;
+use std::ops::Add;
+
+/// The nodes a search reached, each with its cost and the node it was reached from.
+///
+/// Doubles as the visited set of the search.
+#[derive(Clone, Debug)]
+pub struct Reached<N, C> {
+    nodes: HashMap<N, (Option<N>, C)>,
+}
+
+impl<N: Clone + Eq + Hash, C: Copy> Reached<N, C> {
+    fn new(start: N, zero: C) -> Self {
+        Self {
+            nodes: HashMap::from([(start, (None, zero))]),
+        }
+    }
+
+    pub fn contains(&self, node: &N) -> bool {
+        self.nodes.contains_key(node)
+    }
+
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    /// Cost of the best known way to `node`.
+    pub fn cost(&self, node: &N) -> Option<C> {
+        self.nodes.get(node).map(|&(_, c)| c)
+    }
+
+    /// The node `node` was reached from, `None` for the start and unreached nodes.
+    pub fn parent(&self, node: &N) -> Option<&N> {
+        self.nodes.get(node)?.0.as_ref()
+    }
+
+    /// The way from the start to `node`, both included.
+    pub fn path(&self, node: &N) -> Option<Vec<N>> {
+        let mut path = vec![node.clone()];
+        let mut current = self.nodes.get(node)?;
+        while let Some(parent) = &current.0 {
+            path.push(parent.clone());
+            current = &self.nodes[parent];
+        }
+        path.reverse();
+        Some(path)
+    }
+
+    /// All reached nodes with their costs, in no particular order.
+    pub fn iter(&self) -> impl Iterator<Item = (&N, C)> {
+        self.nodes.iter().map(|(n, &(_, c))| (n, c))
+    }
+}
+
+/// Explores everything reachable from `start` breadth first, so that the cost of
+/// each node is the least number of steps to it.
+pub fn bfs_reach<N, I>(start: N, mut neighbors: impl FnMut(&N) -> I) -> Reached<N, usize>
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = N>,
+{
+    let mut reached = Reached::new(start.clone(), 0);
+    let mut queue = VecDeque::from([start]);
+    while let Some(node) = queue.pop_front() {
+        let cost = reached.cost(&node).unwrap() + 1;
+        for next in neighbors(&node) {
+            if let Entry::Vacant(e) = reached.nodes.entry(next.clone()) {
+                e.insert((Some(node.clone()), cost));
+                queue.push_back(next);
+            }
+        }
+    }
+    reached
+}
+
+/// A path with the least number of steps from `start` to a node satisfying `goal`.
+pub fn bfs<N, I>(
+    start: N,
+    mut neighbors: impl FnMut(&N) -> I,
+    mut goal: impl FnMut(&N) -> bool,
+) -> Option<Vec<N>>
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = N>,
+{
+    if goal(&start) {
+        return Some(vec![start]);
+    }
+    let mut reached = Reached::new(start.clone(), 0);
+    let mut queue = VecDeque::from([start]);
+    while let Some(node) = queue.pop_front() {
+        let cost = reached.cost(&node).unwrap() + 1;
+        for next in neighbors(&node) {
+            if let Entry::Vacant(e) = reached.nodes.entry(next.clone()) {
+                e.insert((Some(node.clone()), cost));
+                if goal(&next) {
+                    return reached.path(&next);
+                }
+                queue.push_back(next);
+            }
+        }
+    }
+    None
+}
+
+/// Explores everything reachable from `start` depth first; the cost of each
+/// node is its depth in the search tree.
+pub fn dfs_reach<N, I>(start: N, mut neighbors: impl FnMut(&N) -> I) -> Reached<N, usize>
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = N>,
+{
+    let mut reached = Reached::new(start.clone(), 0);
+    let mut stack = vec![(start, None, 0)];
+    let mut done = HashSet::new();
+    while let Some((node, parent, depth)) = stack.pop() {
+        if !done.insert(node.clone()) {
+            continue;
+        }
+        reached.nodes.insert(node.clone(), (parent, depth));
+        for next in neighbors(&node) {
+            if !done.contains(&next) {
+                stack.push((next, Some(node.clone()), depth + 1));
+            }
+        }
+    }
+    reached
+}
+
+/// Some path from `start` to a node satisfying `goal`, found depth first.
+pub fn dfs<N, I>(
+    start: N,
+    mut neighbors: impl FnMut(&N) -> I,
+    mut goal: impl FnMut(&N) -> bool,
+) -> Option<Vec<N>>
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = N>,
+{
+    let mut reached = Reached::new(start.clone(), 0);
+    let mut stack = vec![(start, None, 0)];
+    let mut done = HashSet::new();
+    while let Some((node, parent, depth)) = stack.pop() {
+        if !done.insert(node.clone()) {
+            continue;
+        }
+        reached.nodes.insert(node.clone(), (parent, depth));
+        if goal(&node) {
+            return reached.path(&node);
+        }
+        for next in neighbors(&node) {
+            if !done.contains(&next) {
+                stack.push((next, Some(node.clone()), depth + 1));
+            }
+        }
+    }
+    None
+}
+
+/// Best-first search shared by Dijkstra and A*, stopping at the first goal popped.
+///
+/// Returns everything settled so far and the goal, if one was reached.
+fn best_first<N, C, I>(
+    start: N,
+    mut neighbors: impl FnMut(&N) -> I,
+    mut heuristic: impl FnMut(&N) -> C,
+    mut goal: impl FnMut(&N) -> bool,
+) -> (Reached<N, C>, Option<N>)
+where
+    N: Clone + Eq + Hash,
+    C: Copy + Ord + Default + Add<Output = C>,
+    I: IntoIterator<Item = (N, C)>,
+{
+    let mut reached = Reached::new(start.clone(), C::default());
+    // The heap holds indices into `nodes`, so nodes need not be ordered.
+    let mut nodes = vec![start.clone()];
+    let mut heap = BinaryHeap::from([Reverse((heuristic(&start), C::default(), 0))]);
+    while let Some(Reverse((_, cost, i))) = heap.pop() {
+        let node = nodes[i].clone();
+        if reached.cost(&node).is_some_and(|best| best < cost) {
+            continue;
+        }
+        if goal(&node) {
+            return (reached, Some(node));
+        }
+        for (next, step) in neighbors(&node) {
+            let next_cost = cost + step;
+            if reached.cost(&next).is_some_and(|best| best <= next_cost) {
+                continue;
+            }
+            reached
+                .nodes
+                .insert(next.clone(), (Some(node.clone()), next_cost));
+            heap.push(Reverse((
+                next_cost + heuristic(&next),
+                next_cost,
+                nodes.len(),
+            )));
+            nodes.push(next);
+        }
+    }
+    (reached, None)
+}
+
+/// Explores everything reachable from `start`, where `neighbors` yields each
+/// neighbor with the non-negative cost of stepping to it.
+pub fn dijkstra_reach<N, C, I>(start: N, neighbors: impl FnMut(&N) -> I) -> Reached<N, C>
+where
+    N: Clone + Eq + Hash,
+    C: Copy + Ord + Default + Add<Output = C>,
+    I: IntoIterator<Item = (N, C)>,
+{
+    best_first(start, neighbors, |_| C::default(), |_| false).0
+}
+
+/// A cheapest path from `start` to a node satisfying `goal`, with its cost.
+pub fn dijkstra<N, C, I>(
+    start: N,
+    neighbors: impl FnMut(&N) -> I,
+    goal: impl FnMut(&N) -> bool,
+) -> Option<(Vec<N>, C)>
+where
+    N: Clone + Eq + Hash,
+    C: Copy + Ord + Default + Add<Output = C>,
+    I: IntoIterator<Item = (N, C)>,
+{
+    astar(start, neighbors, |_| C::default(), goal)
+}
+
+/// Like [`dijkstra`], guided by `heuristic`, which must never overestimate the
+/// remaining cost to a goal.
+pub fn astar<N, C, I>(
+    start: N,
+    neighbors: impl FnMut(&N) -> I,
+    heuristic: impl FnMut(&N) -> C,
+    goal: impl FnMut(&N) -> bool,
+) -> Option<(Vec<N>, C)>
+where
+    N: Clone + Eq + Hash,
+    C: Copy + Ord + Default + Add<Output = C>,
+    I: IntoIterator<Item = (N, C)>,
+{
+    let (reached, end) = best_first(start, neighbors, heuristic, goal);
+    let end = end?;
+    Some((reached.path(&end)?, reached.cost(&end)?))
+}
+
+/// Number of distinct paths from `start` to nodes satisfying `goal`, which end
+/// a path when reached.
+///
+/// Panics if the part of the graph reachable from `start` has a cycle.
+pub fn count_paths<N, I>(
+    start: N,
+    mut neighbors: impl FnMut(&N) -> I,
+    mut goal: impl FnMut(&N) -> bool,
+) -> usize
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = N>,
+{
+    let mut counts: HashMap<N, usize> = HashMap::new();
+    let mut in_progress = HashSet::new();
+    let mut stack = vec![(start.clone(), false)];
+    while let Some((node, expanded)) = stack.pop() {
+        if counts.contains_key(&node) {
+            continue;
+        }
+        if goal(&node) {
+            counts.insert(node, 1);
+        } else if expanded {
+            // All successors were counted before returning to this node.
+            let n = neighbors(&node).into_iter().map(|n| counts[&n]).sum();
+            in_progress.remove(&node);
+            counts.insert(node, n);
+        } else {
+            assert!(in_progress.insert(node.clone()), "Cycle in count_paths");
+            stack.push((node.clone(), true));
+            for next in neighbors(&node) {
+                if !counts.contains_key(&next) {
+                    stack.push((next, false));
+                }
+            }
+        }
+    }
+    counts[&start]
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::geometry::{Direction4, Point};
+    use crate::grid::Grid;
+    static INP: &str = "..#.
+.#..
+...#
+#...
+";
+
+    fn maze() -> Grid<char> {
+        Grid::parse(INP, Ok).unwrap()
+    }
+
+    fn free(maze: &Grid<char>, p: &Point) -> Vec<Point> {
+        Direction4::ALL
+            .into_iter()
+            .filter_map(|d| maze.step(*p, d.vec()))
+            .filter(|&n| maze[n] == '.')
+            .collect()
+    }
+
+    #[test]
+    fn test_bfs() {
+        let m = maze();
+        let goal = Point::new(3, 3);
+        let path = bfs(Point::new(0, 0), |p| free(&m, p), |p| *p == goal).unwrap();
+        assert_eq!(path.len(), 7);
+        assert_eq!(path[0], Point::new(0, 0));
+        assert_eq!(path[6], goal);
+        assert!(path
+            .windows(2)
+            .all(|w| (w[1] - w[0]).x.abs() + (w[1] - w[0]).y.abs() == 1));
+        assert_eq!(bfs(Point::new(0, 0), |p| free(&m, p), |_| false), None);
+
+        let reached = bfs_reach(Point::new(0, 0), |p| free(&m, p));
+        assert_eq!(reached.len(), 12);
+        assert_eq!(reached.cost(&goal), Some(6));
+        assert_eq!(reached.path(&goal).unwrap().len(), 7);
+        assert!(!reached.contains(&Point::new(2, 0)));
+    }
+
+    #[test]
+    fn test_dfs() {
+        let m = maze();
+        let goal = Point::new(3, 3);
+        let path = dfs(Point::new(0, 0), |p| free(&m, p), |p| *p == goal).unwrap();
+        assert_eq!(path.first(), Some(&Point::new(0, 0)));
+        assert_eq!(path.last(), Some(&goal));
+        let reached = dfs_reach(Point::new(0, 0), |p| free(&m, p));
+        assert_eq!(reached.len(), 12);
+        let mut depths: Vec<_> = reached.iter().map(|(_, d)| d).collect();
+        depths.sort();
+        assert_eq!(depths[0], 0);
+    }
+
+    /// Small graph where the direct edge is more expensive than the detour.
+    fn weighted(n: &u32) -> Vec<(u32, u32)> {
+        match n {
+            0 => vec![(1, 1), (2, 10)],
+            1 => vec![(2, 2), (3, 7)],
+            2 => vec![(3, 1)],
+            _ => vec![],
+        }
+    }
+
+    #[test]
+    fn test_dijkstra() {
+        assert_eq!(
+            dijkstra(0, weighted, |&n| n == 3),
+            Some((vec![0, 1, 2, 3], 4))
+        );
+        assert_eq!(dijkstra(3, weighted, |&n| n == 0), None);
+        let reached = dijkstra_reach(0, weighted);
+        assert_eq!(reached.cost(&2), Some(3));
+        assert_eq!(reached.parent(&2), Some(&1));
+        assert_eq!(reached.parent(&0), None);
+    }
+
+    #[test]
+    fn test_astar() {
+        let m = maze();
+        let goal = Point::new(3, 3);
+        let manhattan = |p: &Point| {
+            let d = goal - *p;
+            d.x.unsigned_abs() + d.y.unsigned_abs()
+        };
+        let neighbors = |p: &Point| free(&m, p).into_iter().map(|n| (n, 1usize));
+        let (path, cost) = astar(Point::new(0, 0), neighbors, manhattan, |p| *p == goal).unwrap();
+        assert_eq!(cost, 6);
+        assert_eq!(path.len(), 7);
+        assert_eq!(
+            dijkstra(Point::new(0, 0), neighbors, |p| *p == goal).map(|(_, c)| c),
+            Some(6)
+        );
+    }
+
+    #[test]
+    fn test_count_paths() {
+        // Lattice paths through a 3 by 3 grid of nodes.
+        let right_down = |&(x, y): &(u32, u32)| {
+            let mut next = Vec::new();
+            if x < 2 {
+                next.push((x + 1, y));
+            }
+            if y < 2 {
+                next.push((x, y + 1));
+            }
+            next
+        };
+        assert_eq!(count_paths((0, 0), right_down, |&p| p == (2, 2)), 6);
+        assert_eq!(count_paths((0, 0), right_down, |&(x, y)| x + y == 2), 4);
+        assert_eq!(count_paths((0, 0), right_down, |_| false), 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "Cycle")]
+    fn test_count_paths_cycle() {
+        count_paths(0, |&n: &u32| [(n + 1) % 3], |&n| n == 5);
+    }
+}
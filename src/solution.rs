@@ -0,0 +1,200 @@
+use crate::parse::ParseError;
+use crate::render::Frames;
+use crate::rng::Rng;
+use crate::stepper::Stepper;
+use std::fmt;
+
+/// The result of solving one part of a puzzle.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Answer {
+    Int(i128),
+    Str(String),
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Answer::Int(n) => write!(f, "{n}"),
+            Answer::Str(s) => write!(f, "{s}"),
+        }
+    }
+}
+
+impl From<u64> for Answer {
+    fn from(n: u64) -> Self {
+        Answer::Int(n.into())
+    }
+}
+
+impl From<i64> for Answer {
+    fn from(n: i64) -> Self {
+        Answer::Int(n.into())
+    }
+}
+
+impl From<usize> for Answer {
+    fn from(n: usize) -> Self {
+        Answer::Int(n as i128)
+    }
+}
+
+impl From<String> for Answer {
+    fn from(s: String) -> Self {
+        Answer::Str(s)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(s: &str) -> Self {
+        Answer::Str(s.to_string())
+    }
+}
+
+/// A puzzle day, split into parsing and the two parts working on the parsed input.
+pub trait Solution {
+    const DAY: u32;
+    type Input;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError>;
+    fn part1(input: &Self::Input) -> Answer;
+    fn part2(input: &Self::Input) -> Answer;
+
+    /// Slow but straightforward version of [`Solution::part1`], to check faster ones against.
+    ///
+    /// Returns `None` for days without a separate reference implementation.
+    fn part1_reference(_input: &Self::Input) -> Option<Answer> {
+        None
+    }
+
+    /// Slow but straightforward version of [`Solution::part2`], see [`Solution::part1_reference`].
+    fn part2_reference(_input: &Self::Input) -> Option<Answer> {
+        None
+    }
+
+    /// A random, structurally valid puzzle input whose scale grows with `size`.
+    ///
+    /// Returns `None` for days without a generator.
+    fn generate(_rng: &mut Rng, _size: usize) -> Option<String> {
+        None
+    }
+
+    /// Frames showing how the solution unfolds on `input`, a single one for a still image.
+    ///
+    /// Returns `None` for days without a visualisation.
+    fn frames(_input: &Self::Input) -> Option<Frames<'_>> {
+        None
+    }
+
+    /// The simulation behind the solution on `input`, to step through interactively.
+    ///
+    /// Returns `None` for days that are not simulations.
+    fn stepper(_input: &Self::Input) -> Option<Box<dyn Stepper>> {
+        None
+    }
+}
+
+/// Object safe view of a [`Solution`], used to keep all days in one registry.
+pub trait DynSolution: Sync {
+    fn day(&self) -> u32;
+    fn parse(&self, input: &str) -> Result<Box<dyn Parsed>, ParseError>;
+    fn generate(&self, rng: &mut Rng, size: usize) -> Option<String>;
+}
+
+/// A parsed input of some day, ready to be solved.
+pub trait Parsed {
+    fn part1(&self) -> Answer;
+    fn part2(&self) -> Answer;
+    fn part1_reference(&self) -> Option<Answer>;
+    fn part2_reference(&self) -> Option<Answer>;
+    fn frames(&self) -> Option<Frames<'_>>;
+    fn stepper(&self) -> Option<Box<dyn Stepper>>;
+}
+
+struct ParsedInput<S: Solution>(S::Input);
+
+impl<S: Solution> Parsed for ParsedInput<S> {
+    fn part1(&self) -> Answer {
+        S::part1(&self.0)
+    }
+
+    fn part2(&self) -> Answer {
+        S::part2(&self.0)
+    }
+
+    fn part1_reference(&self) -> Option<Answer> {
+        S::part1_reference(&self.0)
+    }
+
+    fn part2_reference(&self) -> Option<Answer> {
+        S::part2_reference(&self.0)
+    }
+
+    fn frames(&self) -> Option<Frames<'_>> {
+        S::frames(&self.0)
+    }
+
+    fn stepper(&self) -> Option<Box<dyn Stepper>> {
+        S::stepper(&self.0)
+    }
+}
+
+impl<S: Solution + Sync + 'static> DynSolution for S {
+    fn day(&self) -> u32 {
+        S::DAY
+    }
+
+    fn parse(&self, input: &str) -> Result<Box<dyn Parsed>, ParseError> {
+        let parsed = S::parse(input).map_err(|e| e.with_day(S::DAY))?;
+        Ok(Box::new(ParsedInput::<S>(parsed)))
+    }
+
+    fn generate(&self, rng: &mut Rng, size: usize) -> Option<String> {
+        S::generate(rng, size)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_answer() {
+        assert_eq!(Answer::from(42u64), Answer::Int(42));
+        assert_eq!(Answer::from(-3i64).to_string(), "-3");
+        assert_eq!(Answer::from(usize::MAX).to_string(), usize::MAX.to_string());
+        assert_eq!(Answer::from("abc").to_string(), "abc");
+    }
+
+    struct Sum;
+
+    impl Solution for Sum {
+        const DAY: u32 = 0;
+        type Input = Vec<u64>;
+
+        fn parse(input: &str) -> Result<Self::Input, ParseError> {
+            input
+                .split(',')
+                .map(|n| n.parse().map_err(|_| ParseError::at(input, n, "a number")))
+                .collect()
+        }
+
+        fn part1(input: &Self::Input) -> Answer {
+            input.iter().sum::<u64>().into()
+        }
+
+        fn part2(input: &Self::Input) -> Answer {
+            input.iter().product::<u64>().into()
+        }
+    }
+
+    #[test]
+    fn test_dyn_solution() {
+        let s: &dyn DynSolution = &Sum;
+        assert_eq!(s.day(), 0);
+        let parsed = s.parse("2,3,4").unwrap();
+        assert_eq!(parsed.part1(), Answer::Int(9));
+        assert_eq!(parsed.part2(), Answer::Int(24));
+        let err = s.parse("2,x").err().unwrap();
+        assert_eq!((err.day, err.line, err.column), (Some(0), 1, 3));
+    }
+}
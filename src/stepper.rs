@@ -0,0 +1,394 @@
+use std::collections::BTreeMap;
+use std::fmt::Write;
+
+/// How many steps apart [`Session`] first keeps snapshots to go back to.
+const CHECKPOINT_INTERVAL: usize = 64;
+
+/// Most snapshots [`Session`] keeps before spacing them twice as far apart.
+const MAX_CHECKPOINTS: usize = 256;
+
+/// Most steps a single command takes forward, so that a simulation going in
+/// circles cannot hang the session.
+pub const MAX_STEPS: usize = 1_000_000;
+
+/// A simulation that can be advanced one step at a time and inspected in between.
+pub trait Stepper: StepperClone {
+    /// Advances by one step, returning `false` if the simulation is over.
+    fn step(&mut self) -> bool;
+
+    /// Text drawing of the current state.
+    fn render(&self) -> String;
+
+    /// Runs a command specific to this simulation, given as whitespace separated words.
+    ///
+    /// Returns `None` for unknown commands, otherwise a message or an error for the user.
+    fn command(&mut self, _words: &[&str]) -> Option<Result<String, String>> {
+        None
+    }
+
+    /// Whether the simulation is back in an earlier state, so that stepping on
+    /// only repeats what came before.
+    fn looping(&self) -> bool {
+        false
+    }
+
+    /// Help on the commands understood by [`Stepper::command`], one per line.
+    fn help(&self) -> &'static str {
+        ""
+    }
+}
+
+/// Lets boxed steppers be cloned, implemented for every `Clone` stepper.
+pub trait StepperClone {
+    fn clone_box(&self) -> Box<dyn Stepper>;
+}
+
+impl<T: Stepper + Clone + 'static> StepperClone for T {
+    fn clone_box(&self) -> Box<dyn Stepper> {
+        Box::new(self.clone())
+    }
+}
+
+const HELP: &str = "\
+next [n], n       step forward, also on an empty line
+prev [n], p       step back
+jump <i>, j       go to iteration i
+                  (going forward stops where the simulation starts repeating,
+                  and after a million steps)
+show, s           draw the current state again
+help, h           show this help
+quit, q           stop
+";
+
+/// What to do after a command.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Reply {
+    Show(String),
+    Quit,
+}
+
+/// Steps a simulation back and forth, replaying from snapshots to go back.
+///
+/// Commands changing the simulation count as happening at the current
+/// iteration, going back before it undoes them.
+pub struct Session {
+    current: Box<dyn Stepper>,
+    iteration: usize,
+    finished: bool,
+    /// Snapshots by iteration, taken every `interval` steps and after every change.
+    checkpoints: BTreeMap<usize, Box<dyn Stepper>>,
+    /// Steps between snapshots, doubled whenever there are too many of them.
+    interval: usize,
+    /// Iterations at which the simulation was changed.
+    edits: Vec<usize>,
+}
+
+impl Session {
+    pub fn new(stepper: Box<dyn Stepper>) -> Self {
+        Self {
+            checkpoints: BTreeMap::from([(0, stepper.clone_box())]),
+            interval: CHECKPOINT_INTERVAL,
+            current: stepper,
+            iteration: 0,
+            finished: false,
+            edits: Vec::new(),
+        }
+    }
+
+    pub fn iteration(&self) -> usize {
+        self.iteration
+    }
+
+    /// Whether the simulation refused to go past the current iteration.
+    pub fn finished(&self) -> bool {
+        self.finished
+    }
+
+    /// Takes up to `n` steps, at most [`MAX_STEPS`], returning how many were taken.
+    ///
+    /// Stops early where the simulation starts repeating, unless it already was.
+    pub fn forward(&mut self, n: usize) -> usize {
+        self.advance(n.min(MAX_STEPS), !self.current.looping())
+    }
+
+    /// Takes up to `n` steps, stopping once the simulation loops if `stop_looping`.
+    fn advance(&mut self, n: usize, stop_looping: bool) -> usize {
+        for taken in 0..n {
+            if self.finished || !self.current.step() {
+                self.finished = true;
+                return taken;
+            }
+            self.iteration += 1;
+            if self.iteration.is_multiple_of(self.interval) {
+                self.checkpoint();
+            }
+            if stop_looping && self.current.looping() {
+                return taken + 1;
+            }
+        }
+        n
+    }
+
+    /// Snapshots the current state, thinning out older snapshots to keep
+    /// their number bounded however long the session runs.
+    fn checkpoint(&mut self) {
+        self.checkpoints
+            .entry(self.iteration)
+            .or_insert_with(|| self.current.clone_box());
+        if self.checkpoints.len() > MAX_CHECKPOINTS {
+            self.interval *= 2;
+            let (interval, edits) = (self.interval, &self.edits);
+            // Snapshots of edits are the only record of them.
+            self.checkpoints
+                .retain(|i, _| i.is_multiple_of(interval) || edits.contains(i));
+        }
+    }
+
+    /// Goes back `n` steps, at most to the start.
+    pub fn back(&mut self, n: usize) {
+        self.jump(self.iteration.saturating_sub(n));
+    }
+
+    /// Goes to `iteration`, or as close as [`Session::forward`] gets.
+    pub fn jump(&mut self, iteration: usize) {
+        if iteration >= self.iteration {
+            self.forward(iteration - self.iteration);
+            return;
+        }
+        if self.edits.iter().any(|&e| e > iteration) {
+            self.edits.retain(|&e| e <= iteration);
+            self.checkpoints.split_off(&(iteration + 1));
+        }
+        let (&start, snapshot) = self.checkpoints.range(..=iteration).next_back().unwrap();
+        self.current = snapshot.clone_box();
+        self.iteration = start;
+        self.finished = false;
+        // Replays steps taken before, however many and wherever they went.
+        self.advance(iteration - start, false);
+    }
+
+    /// Records that the current state was changed, making later snapshots stale.
+    fn changed(&mut self) {
+        self.checkpoints.split_off(&(self.iteration + 1));
+        self.checkpoints
+            .insert(self.iteration, self.current.clone_box());
+        self.edits.push(self.iteration);
+        self.finished = false;
+    }
+
+    /// The current iteration and state.
+    pub fn view(&self) -> String {
+        let mut out = format!("Iteration {}", self.iteration);
+        if self.finished {
+            out += " (finished)";
+        }
+        let _ = write!(out, "\n{}", self.current.render());
+        out
+    }
+
+    /// Runs one line of user input.
+    pub fn execute(&mut self, line: &str) -> Result<Reply, String> {
+        let words: Vec<_> = line.split_whitespace().collect();
+        let count = |default: usize| match words.get(1) {
+            Some(n) => n.parse().map_err(|_| format!("{n} is not a number")),
+            None => Ok(default),
+        };
+        let target = match words.first().copied().unwrap_or("next") {
+            "next" | "n" => self.iteration.saturating_add(count(1)?),
+            "prev" | "p" => {
+                self.back(count(1)?);
+                self.iteration
+            }
+            "jump" | "j" => {
+                let target = count(usize::MAX)?;
+                if target == usize::MAX {
+                    return Err("jump needs an iteration".to_string());
+                }
+                target
+            }
+            "show" | "s" => self.iteration,
+            "help" | "h" | "?" => {
+                return Ok(Reply::Show(format!("{HELP}{}", self.current.help())));
+            }
+            "quit" | "q" | "exit" => return Ok(Reply::Quit),
+            _ => {
+                let message = self
+                    .current
+                    .command(&words)
+                    .ok_or_else(|| format!("Unknown command {}, try help", words[0]))??;
+                self.changed();
+                return Ok(Reply::Show(format!("{}\n{message}", self.view())));
+            }
+        };
+        let start = self.iteration;
+        self.jump(target);
+        if self.iteration < target && !self.finished {
+            let reason = if self.iteration - start < MAX_STEPS {
+                "the simulation started repeating"
+            } else {
+                "taking the most steps one command takes"
+            };
+            return Ok(Reply::Show(format!(
+                "{}\nStopped before iteration {target}: {reason}",
+                self.view()
+            )));
+        }
+        Ok(Reply::Show(self.view()))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    /// Counts up to a limit that can be changed on the way.
+    #[derive(Clone)]
+    struct Counter {
+        value: usize,
+        limit: usize,
+    }
+
+    impl Stepper for Counter {
+        fn step(&mut self) -> bool {
+            if self.value == self.limit {
+                return false;
+            }
+            self.value += 1;
+            true
+        }
+
+        fn render(&self) -> String {
+            self.value.to_string()
+        }
+
+        fn command(&mut self, words: &[&str]) -> Option<Result<String, String>> {
+            match words {
+                ["add", n] => {
+                    self.value += n.parse::<usize>().ok()?;
+                    Some(Ok(format!("Added {n}")))
+                }
+                _ => None,
+            }
+        }
+    }
+
+    /// Goes round a clock face forever.
+    #[derive(Clone)]
+    struct Clock {
+        steps: usize,
+        hours: usize,
+    }
+
+    impl Stepper for Clock {
+        fn step(&mut self) -> bool {
+            self.steps += 1;
+            true
+        }
+
+        fn render(&self) -> String {
+            (self.steps % self.hours).to_string()
+        }
+
+        fn command(&mut self, _: &[&str]) -> Option<Result<String, String>> {
+            None
+        }
+
+        fn looping(&self) -> bool {
+            self.steps >= self.hours
+        }
+    }
+
+    fn session(limit: usize) -> Session {
+        Session::new(Box::new(Counter { value: 0, limit }))
+    }
+
+    #[test]
+    fn test_forward_and_back() {
+        let mut s = session(1000);
+        assert_eq!(s.forward(200), 200);
+        s.back(70);
+        assert_eq!(
+            (s.iteration(), s.view()),
+            (130, "Iteration 130\n130".into())
+        );
+        s.jump(5);
+        assert_eq!(s.view(), "Iteration 5\n5");
+        s.back(10);
+        assert_eq!(s.iteration(), 0);
+        s.jump(2000);
+        assert_eq!(s.iteration(), 1000);
+        assert!(s.finished());
+        assert_eq!(s.view(), "Iteration 1000 (finished)\n1000");
+        s.back(1);
+        assert!(!s.finished());
+        assert_eq!(s.view(), "Iteration 999\n999");
+    }
+
+    #[test]
+    fn test_commands() {
+        let mut s = session(1000);
+        let show = |r: Result<Reply, String>| match r.unwrap() {
+            Reply::Show(s) => s,
+            Reply::Quit => panic!("Quit"),
+        };
+        assert_eq!(show(s.execute("n 3")), "Iteration 3\n3");
+        assert_eq!(show(s.execute("")), "Iteration 4\n4");
+        assert_eq!(show(s.execute("p")), "Iteration 3\n3");
+        assert_eq!(show(s.execute("jump 128")), "Iteration 128\n128");
+        assert_eq!(show(s.execute("add 10")), "Iteration 128\n138\nAdded 10");
+        // Replaying from the edited snapshot keeps the edit.
+        assert_eq!(show(s.execute("n 2")), "Iteration 130\n140");
+        assert_eq!(show(s.execute("p 2")), "Iteration 128\n138");
+        // Going back before an edit undoes it.
+        assert_eq!(show(s.execute("j 100")), "Iteration 100\n100");
+        assert_eq!(show(s.execute("j 130")), "Iteration 130\n130");
+        assert_eq!(show(s.execute("p 2")), "Iteration 128\n128");
+        // Edits at iterations between snapshots survive going back to them.
+        assert_eq!(show(s.execute("p 28")), "Iteration 100\n100");
+        assert_eq!(show(s.execute("add 1")), "Iteration 100\n101\nAdded 1");
+        assert_eq!(show(s.execute("n 5")), "Iteration 105\n106");
+        assert_eq!(show(s.execute("j 100")), "Iteration 100\n101");
+        assert!(show(s.execute("help")).contains("jump"));
+        assert_eq!(
+            s.execute("jump"),
+            Err("jump needs an iteration".to_string())
+        );
+        assert_eq!(s.execute("n x"), Err("x is not a number".to_string()));
+        assert!(s.execute("fly").is_err());
+        assert_eq!(s.execute("q"), Ok(Reply::Quit));
+    }
+
+    #[test]
+    fn test_stops_looping() {
+        let mut s = Session::new(Box::new(Clock {
+            steps: 0,
+            hours: 12,
+        }));
+        let show = |r: Result<Reply, String>| match r.unwrap() {
+            Reply::Show(s) => s,
+            Reply::Quit => panic!("Quit"),
+        };
+        assert_eq!(
+            show(s.execute("jump 1000000000")),
+            "Iteration 12\n0\nStopped before iteration 1000000000: the simulation started repeating"
+        );
+        // Once it is looping, it goes on as far as asked, up to the limit.
+        assert_eq!(show(s.execute("n 100")), "Iteration 112\n4");
+        assert_eq!(
+            show(s.execute("n 99999999999")),
+            format!(
+                "Iteration {}\n8\nStopped before iteration 100000000111: \
+                 taking the most steps one command takes",
+                MAX_STEPS + 112
+            )
+        );
+        assert!(s.checkpoints.len() <= MAX_CHECKPOINTS);
+        // Going back replays steps taken before, past where the loop started.
+        assert_eq!(show(s.execute("j 500001")), "Iteration 500001\n9");
+        assert_eq!(show(s.execute("j 5")), "Iteration 5\n5");
+        assert_eq!(
+            show(s.execute("j 500000")),
+            "Iteration 12\n0\nStopped before iteration 500000: the simulation started repeating"
+        );
+    }
+}